@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use nate_codegen::{input_into_blocks, Block, CompileError, SpanInput, Strip};
+use nom::error::ErrorKind;
+
+use crate::{project_root, value, Error};
+
+/// The extensions of the files in directories that are checked, if `--ext` is not supplied
+///
+/// E.g. `.po` and `.ftl` files next to the templates are no templates.
+const DEFAULT_EXTENSIONS: &str = "html,htm,xml,svg,txt,md,css,js,json,yaml,yml,toml,j2,jinja";
+
+/// `nate check [--json] [--root <DIR>] [--line-statement <PREFIX>] [--ext <EXT,…>] [<PATH>…]`
+pub(crate) fn main(args: &[String]) -> Result<(), Error> {
+    let mut json = false;
+    let mut root = ".";
+    let mut line_statement = None;
+    let mut extensions = DEFAULT_EXTENSIONS;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--root" => root = value(arg, args.next())?,
+            "--ext" => extensions = value(arg, args.next())?,
+            "--line-statement" => line_statement = Some(value(arg, args.next())?.to_owned()),
+            _ if arg.starts_with('-') => {
+                return Err(Error::Usage(format!("unknown option {:?}", arg)));
+            },
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    if paths.is_empty() {
+        paths.push("templates");
+    }
+    let root = project_root(root)?;
+
+    let extensions = extensions
+        .split(',')
+        .map(|ext| ext.trim().trim_start_matches('.'))
+        .filter(|ext| !ext.is_empty())
+        .collect::<Vec<_>>();
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(root.join(path), &extensions, true, &mut files)?;
+    }
+    let mut checker = Checker {
+        root,
+        line_statement,
+        checked: HashSet::new(),
+        stack: Vec::new(),
+        problems: Vec::new(),
+    };
+    for file in files {
+        checker.check_file(file);
+    }
+
+    let problems = checker.problems;
+    if json {
+        println!("{}", to_json(&problems));
+    } else {
+        for problem in &problems {
+            println!("{}", problem);
+        }
+    }
+    match problems.len() {
+        0 => Ok(()),
+        1 => Err(Error::Failed("found 1 problem".to_owned())),
+        len => Err(Error::Failed(format!("found {} problems", len))),
+    }
+}
+
+/// Collect the files in `path` recursively, in a stable order
+///
+/// Files in directories are only collected if they have one of the `extensions`, files that were
+/// named on the command line are always collected.
+fn collect_files(
+    path: PathBuf,
+    extensions: &[&str],
+    explicit: bool,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), Error> {
+    let open_error = |err| Error::Failed(format!("could not open {:?}: {}", path, err));
+    if !path.metadata().map_err(open_error)?.is_dir() {
+        let is_template = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| {
+                extensions.iter().any(|e| e.eq_ignore_ascii_case(ext))
+            });
+        if explicit || is_template {
+            files.push(path);
+        }
+        return Ok(());
+    }
+
+    let mut entries = read_dir(&path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| Ok(entry?.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(open_error)?;
+    entries.sort();
+    for entry in entries {
+        collect_files(entry, extensions, false, files)?;
+    }
+    Ok(())
+}
+
+/// A problem that was found in a template
+#[derive(Debug)]
+struct Problem {
+    /// The path of the template, relative to the project root
+    path: String,
+    row: u32,
+    column: usize,
+    kind: ProblemKind,
+    message: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ProblemKind {
+    /// The template could not be read
+    Io,
+    /// The template could not be parsed
+    Syntax,
+    /// A data block `{{…}}` or an include `{<…>}` is empty
+    EmptyBlock,
+    /// The target of an include does not exist
+    MissingInclude,
+    /// An include includes itself, directly or indirectly
+    IncludeCycle,
+}
+
+impl ProblemKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ProblemKind::Io => "io",
+            ProblemKind::Syntax => "syntax",
+            ProblemKind::EmptyBlock => "empty-block",
+            ProblemKind::MissingInclude => "missing-include",
+            ProblemKind::IncludeCycle => "include-cycle",
+        }
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {} [{}]",
+            self.path,
+            self.row,
+            self.column,
+            self.message,
+            self.kind.as_str(),
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Checker {
+    root: PathBuf,
+    line_statement: Option<String>,
+    /// The canonical paths of all templates that were checked or are being checked
+    checked: HashSet<PathBuf>,
+    /// The canonical paths of the templates that are being checked, the last one includes the next
+    stack: Vec<PathBuf>,
+    problems: Vec<Problem>,
+}
+
+impl Checker {
+    fn check_file(&mut self, path: PathBuf) {
+        let path = path.canonicalize().unwrap_or(path);
+        if !self.checked.insert(path.clone()) {
+            return;
+        }
+
+        let source = match read_to_string(&path) {
+            Ok(source) => source,
+            Err(err) => {
+                self.problems.push(Problem {
+                    path: self.display(&path),
+                    row: 1,
+                    column: 1,
+                    kind: ProblemKind::Io,
+                    message: format!("could not read the template: {}", err),
+                });
+                return;
+            },
+        };
+
+        self.stack.push(path.clone());
+        let span = SpanInput::new_with_shared(source, Some(path.clone().into()));
+        for block in input_into_blocks(span, Strip::default(), self.line_statement.clone()) {
+            match block {
+                Ok(Block::Include(include)) => self.check_include(&path, include),
+                Ok(_) => {},
+                Err(CompileError::Nom(nom::Err::Error(err) | nom::Err::Failure(err))) => {
+                    let (kind, message) = match err.code {
+                        ErrorKind::NonEmpty => (ProblemKind::EmptyBlock, "empty block".to_owned()),
+                        ErrorKind::Verify if err.input.starts_with('"') => (
+                            ProblemKind::Syntax,
+                            format!("unknown escaper {}", err.input.as_str()),
+                        ),
+                        ErrorKind::Verify => (
+                            ProblemKind::Syntax,
+                            format!(
+                                "invalid filter or format specifier {:?}",
+                                err.input.as_str()
+                            ),
+                        ),
+                        code => (
+                            ProblemKind::Syntax,
+                            format!("could not parse the template ({:?})", code),
+                        ),
+                    };
+                    self.push(&err.input, kind, message);
+                },
+                Err(err) => self.push(&SpanInput::new(""), ProblemKind::Syntax, err.to_string()),
+            }
+        }
+        let _ = self.stack.pop();
+    }
+
+    /// Resolve an include like the code generation does, and check its target
+    fn check_include(&mut self, path: &Path, include: SpanInput) {
+        let include_path = include.as_str().trim();
+        let target = nate_codegen::include_path(&self.root, path, include_path);
+        let target = match target.canonicalize() {
+            Ok(target) => target,
+            Err(_) => {
+                let message = format!("missing include target {:?}", include_path);
+                self.push(&include, ProblemKind::MissingInclude, message);
+                return;
+            },
+        };
+
+        match self.stack.iter().position(|p| *p == target) {
+            Some(start) => {
+                let mut message = "include cycle: ".to_owned();
+                for p in &self.stack[start..] {
+                    write!(message, "{} -> ", self.display(p)).unwrap();
+                }
+                message.push_str(&self.display(&target));
+                self.push(&include, ProblemKind::IncludeCycle, message);
+            },
+            None => self.check_file(target),
+        }
+    }
+
+    fn push(&mut self, span: &SpanInput, kind: ProblemKind, message: String) {
+        let path = match span.get_shared() {
+            Some(path) => self.display(path),
+            None => self.display(self.stack.last().map_or(Path::new(""), PathBuf::as_path)),
+        };
+        self.problems.push(Problem {
+            path,
+            row: span.location_line(),
+            column: span.naive_get_utf8_column(),
+            kind,
+            message,
+        });
+    }
+
+    /// The path relative to the project root if possible
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+/// `[{"path": …, "row": …, "column": …, "kind": …, "message": …}, …]`
+fn to_json(problems: &[Problem]) -> String {
+    let mut output = "[".to_owned();
+    for (index, problem) in problems.iter().enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+        write!(
+            output,
+            "{{\"path\":{},\"row\":{},\"column\":{},\"kind\":{},\"message\":{}}}",
+            json_string(&problem.path),
+            problem.row,
+            problem.column,
+            json_string(problem.kind.as_str()),
+            json_string(&problem.message),
+        )
+        .unwrap();
+    }
+    output.push(']');
+    output
+}
+
+fn json_string(s: &str) -> String {
+    let mut output = String::with_capacity(s.len() + 2);
+    output.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
@@ -0,0 +1,148 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use nate_codegen::expand;
+use syn::DeriveInput;
+
+use crate::{project_root, value, Error};
+
+/// The macro calls that mark the annotations while the code is formatted
+const MARKER: &str = "__nate_addr!";
+
+/// `nate expand [--annotate] [--root <DIR>] [--settings <SETTINGS>] <TEMPLATE>`
+pub(crate) fn main(args: &[String]) -> Result<(), Error> {
+    let mut annotate = false;
+    let mut root = ".";
+    let mut settings = None;
+    let mut template = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--annotate" => annotate = true,
+            "--root" => root = value(arg, args.next())?,
+            "--settings" => settings = Some(value(arg, args.next())?),
+            _ if arg.starts_with('-') => {
+                return Err(Error::Usage(format!("unknown option {:?}", arg)));
+            },
+            _ if template.is_some() => {
+                return Err(Error::Usage(format!("unexpected argument {:?}", arg)));
+            },
+            _ => template = Some(arg.as_str()),
+        }
+    }
+    let template = template.ok_or_else(|| Error::Usage("missing template".to_owned()))?;
+    let root = project_root(root)?;
+
+    let mut attr = format!("path = {:?}", template);
+    if let Some(settings) = settings {
+        write!(attr, ", {}", settings).unwrap();
+    }
+    let ast = syn::parse_str::<DeriveInput>(&format!("#[template({})] struct Template;", attr))
+        .map_err(|err| Error::Usage(format!("could not parse the settings: {}", err)))?;
+    let (content, _) = expand(&ast, &root).map_err(|err| Error::Failed(err.to_string()))?;
+
+    let code = match annotate {
+        true => format_annotated(&content, &root),
+        false => None,
+    };
+    let code = match code.or_else(|| format(&content)) {
+        Some(code) => code,
+        None => {
+            eprintln!("warning: the generated code could not be parsed, so it is not formatted");
+            content
+        },
+    };
+    print!("{}", code);
+    Ok(())
+}
+
+/// Format the generated code with prettyplease
+fn format(content: &str) -> Option<String> {
+    let file = syn::parse_file(&format!("const _: () = {};", content)).ok()?;
+    Some(prettyplease::unparse(&file))
+}
+
+/// Format the generated code, and replace the annotations with comments `// path:row:column`
+///
+/// The comments of the generated code do not survive the formatting, so they are replaced with
+/// macro calls first. Annotations that are not in front of a statement are dropped.
+fn format_annotated(content: &str, root: &Path) -> Option<String> {
+    let root = root.to_string_lossy();
+    let mut locations = Vec::new();
+    let mut marked = String::with_capacity(content.len());
+    let mut last = "";
+    let mut lines = content.lines().peekable();
+    while let Some(line) = lines.next() {
+        let location = line
+            .trim()
+            .strip_prefix("/* #[::nate::addr(")
+            .and_then(|line| line.strip_suffix(")] */"))
+            .and_then(|line| parse_location(line, &root));
+        let location = match location {
+            Some(location) => location,
+            None => {
+                marked.push_str(line);
+                marked.push('\n');
+                if !line.trim().is_empty() {
+                    last = line.trim_end();
+                }
+                continue;
+            },
+        };
+
+        let next = lines.peek().map_or("", |line| line.trim_start());
+        let is_statement = last.ends_with(['{', '}', ';']) && !next.starts_with("else");
+        if is_statement {
+            writeln!(marked, "{}({});", MARKER, locations.len()).unwrap();
+            locations.push(location);
+        }
+    }
+
+    let code = format(&marked)?;
+    let mut output = String::with_capacity(code.len());
+    for line in code.lines() {
+        let index = line
+            .trim_start()
+            .strip_prefix(MARKER)
+            .and_then(|index| index.strip_prefix('('))
+            .and_then(|index| index.strip_suffix(");"))
+            .and_then(|index| index.parse::<usize>().ok());
+        match index {
+            Some(index) => {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                writeln!(output, "{}// {}", indent, locations[index]).unwrap();
+            },
+            None => writeln!(output, "{}", line).unwrap(),
+        }
+    }
+    Some(output)
+}
+
+/// `path="…", offset=…, row=…, col=…` -> `path:row:col`, with the path relative to `root`
+fn parse_location(args: &str, root: &str) -> Option<String> {
+    let mut path = None;
+    let mut row = None;
+    let mut col = None;
+    let args = syn::parse_str::<syn::ExprTuple>(&format!("({},)", args)).ok()?;
+    for arg in args.elems {
+        let (name, value) = match arg {
+            syn::Expr::Assign(assign) => (assign.left, assign.right),
+            _ => continue,
+        };
+        let value = match *value {
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => lit,
+            _ => continue,
+        };
+        match (quote::quote!(#name).to_string().as_str(), value) {
+            ("path", syn::Lit::Str(value)) => path = Some(value.value()),
+            ("row", syn::Lit::Int(value)) => row = Some(value.to_string()),
+            ("col", syn::Lit::Int(value)) => col = Some(value.to_string()),
+            _ => {},
+        }
+    }
+    let path = path?;
+    let path = path
+        .strip_prefix(root)
+        .map_or(path.as_str(), |path| path.trim_start_matches('/'));
+    Some(format!("{}:{}:{}", path, row?, col?))
+}
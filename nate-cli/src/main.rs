@@ -0,0 +1,89 @@
+//! ## `nate` — command line tools for NaTE templates
+//!
+//! ```text
+//! nate expand [--annotate] [--root <DIR>] [--settings <SETTINGS>] <TEMPLATE>
+//! nate check [--json] [--root <DIR>] [--line-statement <PREFIX>] [--ext <EXT,…>] [<PATH>…]
+//! ```
+//!
+//! `nate expand` prints the Rust code that `#[derive(Nate)]` generates for a template.
+//!
+//! `nate check` parses all templates in the paths, and reports syntax errors, empty blocks,
+//! missing include targets and include cycles without compiling anything.
+//!
+//! The code generation is the one of `nate-derive`, both use the library `nate-codegen`.
+
+#![forbid(unsafe_code)]
+
+mod check;
+mod expand;
+
+use std::env::args;
+use std::path::PathBuf;
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: nate <COMMAND> [OPTIONS]
+
+Commands:
+    expand [--annotate] [--root <DIR>] [--settings <SETTINGS>] <TEMPLATE>
+        Print the Rust code that is generated for TEMPLATE
+    check [--json] [--root <DIR>] [--line-statement <PREFIX>] [--ext <EXT,…>] [<PATH>…]
+        Check the templates in the files or directories PATH [default: templates]
+
+Options:
+    --annotate                 Annotate the generated code with the rows of the template
+    --ext <EXT,…>              The extensions of the templates in directories
+                               [default: html,htm,xml,svg,txt,md,css,js,json,yaml,yml,toml,j2,jinja]
+    --json                     Print the problems as a JSON array
+    --line-statement <PREFIX>  The line_statement prefix of the templates
+    --root <DIR>               The project root that paths are relative to [default: .]
+    --settings <SETTINGS>      Further arguments of #[template(…)], e.g. 'strip = \"blocks\"'
+";
+
+/// The error of a command
+#[derive(Debug)]
+enum Error {
+    /// The arguments are wrong, so the usage is printed, too
+    Usage(String),
+    /// The command failed
+    Failed(String),
+}
+
+fn main() {
+    let args = args().skip(1).collect::<Vec<_>>();
+    let result = match args.first().map(String::as_str) {
+        Some("expand") => expand::main(&args[1..]),
+        Some("check") => check::main(&args[1..]),
+        Some("-h" | "--help" | "help") => {
+            print!("{}", USAGE);
+            return;
+        },
+        Some(command) => Err(Error::Usage(format!("unknown command {:?}", command))),
+        None => Err(Error::Usage("missing command".to_owned())),
+    };
+    match result {
+        Ok(()) => {},
+        Err(Error::Usage(err)) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            exit(2);
+        },
+        Err(Error::Failed(err)) => {
+            eprintln!("error: {}", err);
+            exit(1);
+        },
+    }
+}
+
+/// The project root `root`, i.e. the directory that template paths are relative to
+fn project_root(root: &str) -> Result<PathBuf, Error> {
+    std::fs::canonicalize(root)
+        .map_err(|err| Error::Failed(format!("could not open {:?}: {}", root, err)))
+}
+
+/// The value of a command line option
+fn value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a str, Error> {
+    match value {
+        Some(value) => Ok(value),
+        None => Err(Error::Usage(format!("missing value of {}", option))),
+    }
+}
@@ -6,21 +6,33 @@ use nom::Offset;
 
 use crate::generate::SpanInput;
 
+/// An error that prevents the code generation of a template
 #[derive(Debug)]
-pub(crate) enum CompileError {
+pub enum CompileError {
+    /// The template could not be parsed
     Nom(nom::Err<nom::error::Error<SpanInput>>),
+    /// The arguments of `#[template(…)]` are invalid
     Darling(darling::Error),
+    /// The input of the derive macro could not be parsed
     Syn(syn::Error),
-    Lex(proc_macro::LexError),
+    /// The generated code could not be formatted
     Fmt(std::fmt::Error),
+    /// A file could not be accessed
     IoError(IoOp, PathBuf, std::io::Error),
+    /// The template is invalid, e.g. a translated message has a missing argument
+    Template(SpanInput, String),
 }
 
+/// The file operation of a [`CompileError::IoError`]
 #[derive(Debug, Clone, Copy)]
-pub(crate) enum IoOp {
+pub enum IoOp {
+    /// Opening the file failed
     Open,
+    /// Querying the metadata of the file failed
     Metadata,
+    /// Reading from the file failed
     Read,
+    /// Writing to the file failed
     Write,
 }
 
@@ -30,9 +42,9 @@ impl std::error::Error for CompileError {
             CompileError::Nom(err) => Some(err),
             CompileError::Darling(err) => Some(err),
             CompileError::Syn(err) => Some(err),
-            CompileError::Lex(err) => Some(err),
             CompileError::Fmt(err) => Some(err),
             CompileError::IoError(_, _, err) => Some(err),
+            CompileError::Template(_, _) => None,
         }
     }
 }
@@ -55,12 +67,6 @@ impl From<syn::Error> for CompileError {
     }
 }
 
-impl From<proc_macro::LexError> for CompileError {
-    fn from(err: proc_macro::LexError) -> Self {
-        Self::Lex(err)
-    }
-}
-
 impl From<std::fmt::Error> for CompileError {
     fn from(err: std::fmt::Error) -> Self {
         Self::Fmt(err)
@@ -73,7 +79,6 @@ impl Display for CompileError {
             CompileError::Fmt(_) => return write!(f, "could not format generated code"),
             CompileError::Darling(err) => return write!(f, "{}", err),
             CompileError::Syn(err) => return write!(f, "{}", err),
-            CompileError::Lex(err) => return write!(f, "could not parse input: {}", err),
             CompileError::IoError(op, path, err) => {
                 let path = current_dir()
                     .ok()
@@ -87,6 +92,10 @@ impl Display for CompileError {
                 };
                 return write!(f, "could not {} {:?}: {}", op, path, err);
             },
+            CompileError::Template(input, msg) => {
+                let (path, row, column) = location(input);
+                return write!(f, "{} in {:?} at row {}, column {}", msg, path, row, column,);
+            },
             CompileError::Nom(err) => err,
         };
 
@@ -95,13 +104,7 @@ impl Display for CompileError {
             nom::Err::Error(err) | nom::Err::Failure(err) => &err.input,
         };
         let source = input.get_source();
-        let path = input
-            .get_shared()
-            .as_deref()
-            .and_then(std::path::Path::to_str)
-            .unwrap_or("??");
-        let row = input.location_line();
-        let column = input.naive_get_utf8_column();
+        let (path, row, column) = location(input);
 
         let source_after = &source[source.offset(input)..];
         let source_after =
@@ -118,3 +121,13 @@ impl Display for CompileError {
         )
     }
 }
+
+/// The path, row and column of a span in a template
+fn location(input: &SpanInput) -> (&str, u32, usize) {
+    let path = input
+        .get_shared()
+        .as_deref()
+        .and_then(std::path::Path::to_str)
+        .unwrap_or("??");
+    (path, input.location_line(), input.naive_get_utf8_column())
+}
@@ -0,0 +1,61 @@
+use darling::FromMeta;
+
+/// The escaper used for `{{ value blocks }}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub(crate) enum Escape {
+    /// Escape XML special characters. This is the default.
+    Html,
+    /// Same as `Html`.
+    Xml,
+    /// Percent-encode the value to be used as a query component in a URL.
+    Url,
+    /// Percent-encode the value to be used as a path segment in a URL.
+    UrlPath,
+    /// Encode the value as `application/x-www-form-urlencoded`.
+    UrlForm,
+    /// Escape the value to be used inside of a JavaScript string literal.
+    Js,
+    /// Escape the value to be used inside of a CSS string.
+    Css,
+    /// Escape the value to be used as a CSS identifier.
+    CssIdent,
+}
+
+impl Default for Escape {
+    fn default() -> Self {
+        Escape::Html
+    }
+}
+
+impl Escape {
+    /// The escaper is the HTML escaper, which prints `RawMarker` values verbatim
+    pub(crate) fn is_html(self) -> bool {
+        matches!(self, Escape::Html | Escape::Xml)
+    }
+
+    /// The trait in `::nate::details` that selects the escaper for displayable values
+    pub(crate) fn kind(self) -> &'static str {
+        match self {
+            Escape::Html | Escape::Xml => "EscapeKind",
+            Escape::Url => "UrlEscapeKind",
+            Escape::UrlPath => "UrlPathEscapeKind",
+            Escape::UrlForm => "UrlFormEscapeKind",
+            Escape::Js => "JsStringEscapeKind",
+            Escape::Css => "CssStringEscapeKind",
+            Escape::CssIdent => "CssIdentEscapeKind",
+        }
+    }
+
+    /// The wrapper type in `::nate::details` that escapes a value
+    pub(crate) fn wrapper(self) -> &'static str {
+        match self {
+            Escape::Html | Escape::Xml => "XmlEscape",
+            Escape::Url => "UrlEscape",
+            Escape::UrlPath => "UrlPathEscape",
+            Escape::UrlForm => "UrlFormEscape",
+            Escape::Js => "JsStringEscape",
+            Escape::Css => "CssStringEscape",
+            Escape::CssIdent => "CssIdentEscape",
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Write;
+
+use darling::FromMeta;
+
+/// The format of floats in `{{ value blocks }}`, e.g. `float(fixed = 2, nan = "–")`
+#[derive(Debug, Default, Clone, FromMeta)]
+pub(crate) struct Float {
+    /// Print exactly this many fraction digits.
+    #[darling(default)]
+    fixed: Option<usize>,
+    /// Print this many significant digits.
+    #[darling(default)]
+    significant: Option<usize>,
+    /// Replacement for `NaN`.
+    #[darling(default)]
+    nan: Option<String>,
+    /// Replacement for `inf`.
+    #[darling(default)]
+    infinity: Option<String>,
+    /// Never use the exponent notation.
+    #[darling(default)]
+    no_exponent: bool,
+}
+
+impl Float {
+    /// Check that the options don't contradict each other
+    pub(crate) fn validate(&self) -> Result<(), darling::Error> {
+        match (self.fixed, self.significant) {
+            (Some(_), Some(_)) => Err(darling::Error::custom(
+                "float: `fixed` and `significant` are mutually exclusive",
+            )),
+            (_, Some(0)) => Err(darling::Error::custom(
+                "float: `significant` must be at least 1",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// A constant expression of type `::nate::fmt::FloatFormat`
+    pub(crate) fn to_expr(&self) -> String {
+        let mut expr = "::nate::fmt::FloatFormat::new()".to_owned();
+        if let Some(fixed) = self.fixed {
+            let _ = write!(expr, ".fixed({})", fixed);
+        }
+        if let Some(significant) = self.significant {
+            let _ = write!(expr, ".significant({})", significant);
+        }
+        if let Some(nan) = &self.nan {
+            let _ = write!(expr, ".nan({:?})", nan);
+        }
+        if let Some(infinity) = &self.infinity {
+            let _ = write!(expr, ".infinity({:?})", infinity);
+        }
+        if self.no_exponent {
+            expr.push_str(".no_exponent()");
+        }
+        expr
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+use fluent_syntax::ast::{
+    CallArguments, Entry, Expression, InlineExpression, Pattern, PatternElement,
+};
+use fluent_syntax::parser::parse;
+use nom::Slice;
+
+use crate::compile_error::{CompileError, IoOp};
+use crate::generate::SpanInput;
+use crate::settings::Context;
+use crate::translate::Message;
+
+/// The messages defined in the `.ftl` files of the project
+#[derive(Debug, Default)]
+pub(crate) struct Messages {
+    /// The variables used by each definition of a message, i.e. by the message in each locale
+    messages: HashMap<String, Vec<BTreeSet<String>>>,
+    /// All `.ftl` files that were read
+    pub(crate) files: Vec<PathBuf>,
+}
+
+impl Messages {
+    /// Read all `.ftl` files in `dir` and its subdirectories
+    pub(crate) fn load(dir: &Path, ctx: &mut Context) -> Result<Self, CompileError> {
+        let mut files = Vec::new();
+        find_files(dir, &mut files)?;
+        files.sort();
+
+        let mut messages = HashMap::<_, Vec<_>>::new();
+        for path in &files {
+            let source = ctx.load_file(path)?;
+            let resource = match parse(source.as_str()) {
+                Ok(resource) => resource,
+                Err((_, errors)) => {
+                    let err = &errors[0];
+                    let span =
+                        SpanInput::new_with_shared(source.clone(), Some(path.clone().into()));
+                    let msg = format!("could not parse Fluent resource: {}", err.kind);
+                    return Err(CompileError::Template(span.slice(err.pos.start..), msg));
+                },
+            };
+
+            let entries = resource
+                .body
+                .iter()
+                .filter_map(|entry| match entry {
+                    Entry::Message(message) => Some((message.id.name, message.value.as_ref())),
+                    _ => None,
+                })
+                .collect::<HashMap<_, _>>();
+            for &id in entries.keys() {
+                let mut variables = BTreeSet::new();
+                collect_message(id, &entries, &mut BTreeSet::new(), &mut variables);
+                messages.entry(id.to_owned()).or_default().push(variables);
+            }
+        }
+        Ok(Self { messages, files })
+    }
+
+    /// Check that `message` is defined, and that its arguments match the variables of the message
+    pub(crate) fn check(&self, message: &Message) -> Result<(), String> {
+        let id = message.id.value();
+        let definitions = match self.messages.get(&id) {
+            Some(definitions) => definitions,
+            None => return Err(format!("unknown Fluent message {:?}", id)),
+        };
+        for arg in &message.args {
+            let name = arg.name.to_string();
+            if !definitions
+                .iter()
+                .any(|variables| variables.contains(&name))
+            {
+                return Err(format!(
+                    "Fluent message {:?} does not use the argument ${}",
+                    id, name,
+                ));
+            }
+        }
+        for variable in definitions.iter().flatten() {
+            if !message.args.iter().any(|arg| arg.name == variable) {
+                return Err(format!(
+                    "missing argument ${} of Fluent message {:?}",
+                    variable, id,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), CompileError> {
+    let entries =
+        read_dir(dir).map_err(|err| CompileError::IoError(IoOp::Open, dir.to_owned(), err))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|err| CompileError::IoError(IoOp::Read, dir.to_owned(), err))?
+            .path();
+        if path.is_dir() {
+            find_files(&path, files)?;
+        } else if path.extension().map_or(false, |ext| ext == "ftl") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Collect the variables of a message and of the messages it references in the same resource
+fn collect_message<'a>(
+    id: &'a str,
+    entries: &HashMap<&'a str, Option<&Pattern<&'a str>>>,
+    seen: &mut BTreeSet<&'a str>,
+    variables: &mut BTreeSet<String>,
+) {
+    if !seen.insert(id) {
+        return;
+    }
+    if let Some(Some(pattern)) = entries.get(id) {
+        let mut references = Vec::new();
+        collect_pattern(pattern, &mut references, variables);
+        for id in references {
+            collect_message(id, entries, seen, variables);
+        }
+    }
+}
+
+fn collect_pattern<'a>(
+    pattern: &Pattern<&'a str>,
+    references: &mut Vec<&'a str>,
+    variables: &mut BTreeSet<String>,
+) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            collect_expression(expression, references, variables);
+        }
+    }
+}
+
+fn collect_expression<'a>(
+    expression: &Expression<&'a str>,
+    references: &mut Vec<&'a str>,
+    variables: &mut BTreeSet<String>,
+) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            collect_inline(selector, references, variables);
+            for variant in variants {
+                collect_pattern(&variant.value, references, variables);
+            }
+        },
+        Expression::Inline(expression) => collect_inline(expression, references, variables),
+    }
+}
+
+fn collect_inline<'a>(
+    expression: &InlineExpression<&'a str>,
+    references: &mut Vec<&'a str>,
+    variables: &mut BTreeSet<String>,
+) {
+    match expression {
+        InlineExpression::VariableReference { id } => {
+            let _ = variables.insert(id.name.to_owned());
+        },
+        InlineExpression::MessageReference {
+            id,
+            attribute: None,
+        } => references.push(id.name),
+        InlineExpression::FunctionReference { arguments, .. } => {
+            collect_arguments(arguments, references, variables);
+        },
+        InlineExpression::Placeable { expression } => {
+            collect_expression(expression, references, variables);
+        },
+        // Terms only see the arguments that are passed explicitly, which cannot be variables.
+        InlineExpression::TermReference { .. }
+        | InlineExpression::MessageReference { .. }
+        | InlineExpression::StringLiteral { .. }
+        | InlineExpression::NumberLiteral { .. } => {},
+    }
+}
+
+fn collect_arguments<'a>(
+    arguments: &CallArguments<&'a str>,
+    references: &mut Vec<&'a str>,
+    variables: &mut BTreeSet<String>,
+) {
+    let named = arguments.named.iter().map(|arg| &arg.value);
+    for expression in arguments.positional.iter().chain(named) {
+        collect_inline(expression, references, variables);
+    }
+}
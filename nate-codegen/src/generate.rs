@@ -0,0 +1,862 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+
+use darling::FromDeriveInput;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::compile_error::CompileError;
+use crate::escape::Escape;
+use crate::minify::Minify;
+use crate::nate_span::SpanStatic;
+use crate::parse::{input_into_blocks, Block, DataSection, Filter, Translation, Value};
+use crate::settings::{Context, Settings};
+#[cfg(any(feature = "fluent", feature = "gettext"))]
+use crate::translate::Message;
+
+/// A part of a template, with the path of the template file
+pub type SpanInput = SpanStatic<(), Option<Cow<'static, Path>>>;
+
+#[derive(Debug)]
+enum ParsedData {
+    Code(Vec<SpanInput>),
+    Data(Vec<DataSection>),
+}
+
+const TAIL: &str = r#"
+            ::nate::details::core::fmt::Result::Ok(())
+        }
+    }
+}
+"#;
+
+/// Generate the implementation of `#[derive(Nate)]` for `ast`
+///
+/// The paths of the template are relative to the project `root`, i.e. the cargo manifest dir.
+/// Returns the generated code, and the context with the hash of the loaded files and the
+/// extracted texts.
+pub fn expand(ast: &DeriveInput, root: &Path) -> Result<(String, Context), CompileError> {
+    let mut ctx = Context {
+        settings: Settings::from_derive_input(ast)?,
+        root: root.to_owned(),
+        ..Default::default()
+    };
+    if ctx.settings.line_statement.as_deref() == Some("") {
+        let err = darling::Error::custom("line_statement must not be empty");
+        return Err(err.with_span(&ast.ident).into());
+    }
+    if cfg!(not(feature = "gettext")) && ctx.settings.pot.is_some() {
+        let err = darling::Error::custom("pot needs the feature `gettext` of `nate`");
+        return Err(err.with_span(&ast.ident).into());
+    }
+    if let Some(context) = &ctx.settings.context {
+        if syn::parse_str::<syn::Type>(context).is_err() {
+            let err = darling::Error::custom("context must be a type");
+            return Err(err.with_span(&ast.ident).into());
+        }
+    }
+    if let Some(float) = &ctx.settings.float {
+        if let Err(err) = float.validate() {
+            return Err(err.with_span(&ast.ident).into());
+        }
+    }
+
+    ctx.minifier = ctx.settings.minify.map(Minify::minifier);
+
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    let ident = &ast.ident;
+
+    let path = ctx.root.join(&ctx.settings.path);
+    let mut content = String::new();
+    writeln!(content, "{{")?;
+    if ctx.settings.context.is_none() {
+        // A template with a render context cannot be displayed without it, so it has no `Display`
+        // implementation, and cannot be used where the context would be lost, e.g. in a filter.
+        write!(
+            content,
+            r#"
+    #[allow(unused_qualifications)]
+    impl {impl_generics} ::nate::details::core::fmt::Display
+        for {ident} {type_generics} {where_clause}
+    {{
+        #[inline]
+        fn fmt(
+            &self,
+            output: &mut ::nate::details::core::fmt::Formatter<'_>,
+        ) -> ::nate::details::core::fmt::Result {{
+            ::nate::RenderInto::render_fmt(self, output)
+        }}
+    }}
+
+    #[allow(unused_qualifications)]
+    impl {impl_generics} ::nate::RawMarker
+        for {ident} {type_generics} {where_clause}
+    {{
+    }}
+"#,
+            impl_generics = quote!(#impl_generics),
+            type_generics = quote!(#type_generics),
+            where_clause = quote!(#where_clause),
+            ident = quote!(#ident),
+        )?;
+    }
+    write!(
+        content,
+        r#"
+    #[allow(unknown_lints)]
+    #[allow(unused_qualifications)]
+    #[allow(clippy::double_parens)]
+    #[allow(clippy::match_single_binding)]
+    #[allow(clippy::needless_borrow)]
+    #[allow(clippy::needless_borrowed_reference)]
+    #[allow(clippy::suspicious_else_formatting)]
+    #[allow(clippy::uninlined_format_args)]
+    #[allow(clippy::used_underscore_binding)]"#,
+    )?;
+    match &ctx.settings.context {
+        // The context is part of the type, so a nested template with another context type,
+        // or a template that is rendered without its context, does not compile.
+        Some(context) => write!(
+            content,
+            r#"
+    impl {impl_generics} ::nate::RenderWith
+        for {ident} {type_generics} {where_clause}
+    {{
+        type Context = {context};
+
+        fn render_with_into(
+            &self,
+            ctx: &{context},
+            mut output: impl ::nate::WriteAny,
+        ) -> ::nate::details::core::fmt::Result {{
+"#,
+            impl_generics = quote!(#impl_generics),
+            type_generics = quote!(#type_generics),
+            where_clause = quote!(#where_clause),
+            ident = quote!(#ident),
+            context = context,
+        )?,
+        None => write!(
+            content,
+            r#"
+    impl {impl_generics} ::nate::RenderInto
+        for {ident} {type_generics} {where_clause}
+    {{
+        fn render_into(
+            &self,
+            mut output: impl ::nate::WriteAny,
+        ) -> ::nate::details::core::fmt::Result {{
+"#,
+            impl_generics = quote!(#impl_generics),
+            type_generics = quote!(#type_generics),
+            where_clause = quote!(#where_clause),
+            ident = quote!(#ident),
+        )?,
+    }
+    if let Some(float) = &ctx.settings.float {
+        writeln!(
+            content,
+            "const _NATE_FLOAT: ::nate::fmt::FloatFormat = {};",
+            float.to_expr(),
+        )?;
+    }
+    parse_file(path, &mut content, &mut ctx)?;
+    #[cfg(feature = "fluent")]
+    if let Some(messages) = &ctx.messages {
+        // recompile the template if a message changes
+        for path in &messages.files {
+            writeln!(
+                content,
+                "const _: &[::nate::details::core::primitive::u8] = \
+                ::nate::details::core::include_bytes!({:?});",
+                path,
+            )?;
+        }
+    }
+    write!(content, "{}", TAIL)?;
+    Ok((content, ctx))
+}
+
+fn parse_file(
+    path: PathBuf,
+    mut output: impl Write,
+    ctx: &mut Context,
+) -> Result<(), CompileError> {
+    use DataSection::{Data, Debug, Escaped, Raw, Verbose};
+
+    let i = ctx.load_file(&path)?;
+    for (block_index, blocks) in parse(path, i, ctx)?.into_iter().enumerate() {
+        let blocks = match blocks {
+            ParsedData::Code(blocks) => {
+                for code in blocks {
+                    writeln!(output, "/* {} */", AddrAnnotation(&code))?;
+                    writeln!(output, "{}", code.as_str())?;
+                }
+                continue;
+            },
+            ParsedData::Data(blocks) => blocks,
+        };
+        let blocks = &blocks[..];
+
+        if let [DataSection::Data(s)] = blocks {
+            writeln!(output, "{{")?;
+            writeln!(output, "/* {} */", AddrAnnotation(s))?;
+            writeln!(output, "    <_ as ::nate::WriteAny>::write_str(")?;
+            writeln!(output, "        &mut output,")?;
+            writeln!(output, "        {:#?},", s.as_str())?;
+            writeln!(output, "    )?;")?;
+            writeln!(output, "}}")?;
+            continue;
+        }
+
+        writeln!(output, "{{")?;
+
+        let has_non_data = blocks.iter().any(|data| !matches!(data, Data(_)));
+        // Values with a format specifier are wrapped, too, if the HTML escaper is used.
+        let has_rendered = blocks.iter().any(|data| match data {
+            Raw(Value { spec, .. }) => spec.is_none(),
+            Escaped(Value {
+                spec, escape: None, ..
+            }) => spec.is_none() || ctx.settings.escape.is_html(),
+            _ => false,
+        });
+
+        if has_rendered {
+            let context = ctx.settings.context.is_some();
+            let float = ctx.settings.float.is_some();
+            write_kind_imports(&mut output, ctx.settings.escape, context, float)?;
+        }
+
+        if has_non_data {
+            // let (_nate_X_Y, …) = (&(expr), …);
+            writeln!(output, "    let (")?;
+            for (data_index, data) in blocks.iter().enumerate() {
+                if !matches!(data, Data(_)) {
+                    writeln!(
+                        output,
+                        "        _nate_{block}_{data},",
+                        block = block_index,
+                        data = data_index,
+                    )?;
+                }
+            }
+            writeln!(output, "    ) = (")?;
+            for data in blocks {
+                match data {
+                    Data(_) => {},
+                    Raw(Value { expr: s, .. })
+                    | Escaped(Value { expr: s, .. })
+                    | Debug(s)
+                    | Verbose(s) => {
+                        writeln!(output, "        /* {} */", AddrAnnotation(s))?;
+                        writeln!(output, "        &({expr}),", expr = s.as_str())?;
+                    },
+                }
+            }
+            writeln!(output, "    );")?;
+
+            // let _nate_X_Y = &filter2(filter1(_nate_X_Y), args2);
+            for (data_index, data) in blocks.iter().enumerate() {
+                let value = match data {
+                    Raw(value) | Escaped(value) if !value.filters.is_empty() => value,
+                    _ => continue,
+                };
+                let mut call = format!(
+                    "_nate_{block}_{data}",
+                    block = block_index,
+                    data = data_index,
+                );
+                for filter in &value.filters {
+                    call = match &filter.args {
+                        Some(args) if is_date_format(filter.name.as_str(), args.as_str()) => {
+                            // the format description is checked at compile time
+                            format!(
+                                "::nate::__nate_date_filter!(date, {}, {})",
+                                call,
+                                args.as_str(),
+                            )
+                        },
+                        Some(args) if !args.is_empty() => {
+                            format!("{}({}, {})", FilterPath(filter), call, args.as_str())
+                        },
+                        _ => format!("{}({})", FilterPath(filter), call),
+                    };
+                }
+                writeln!(
+                    output,
+                    "    let _nate_{block}_{data} = &{{",
+                    block = block_index,
+                    data = data_index,
+                )?;
+                writeln!(
+                    output,
+                    "        /* {} */",
+                    AddrAnnotation(&value.filters[0].name)
+                )?;
+                writeln!(output, "        {}", call)?;
+                writeln!(output, "    }};")?;
+            }
+        }
+
+        // Values are rendered one by one, so that nested templates can render directly into
+        // `output`. All other sections in between are written with a single `write!(…)`.
+        let mut start = 0;
+        for (data_index, data) in blocks.iter().enumerate() {
+            let (s, wrap, in_script) = match data {
+                Raw(Value {
+                    expr: s,
+                    spec: None,
+                    ..
+                }) => (s, "wrap_raw", false),
+                Escaped(Value {
+                    expr: s,
+                    spec: None,
+                    escape: None,
+                    in_script,
+                    ..
+                }) => (s, "wrap", *in_script),
+                _ => continue,
+            };
+            write_sections(
+                &mut output,
+                ctx.settings.escape,
+                block_index,
+                start,
+                &blocks[start..data_index],
+            )?;
+            start = data_index + 1;
+
+            // (&&&&&EscapeWrapper::new(…)).wrap(…).render(…);
+            let value = format!("_nate_{}_{}", block_index, data_index);
+            writeln!(output, "    /* {} */", AddrAnnotation(s))?;
+            if in_script {
+                // `Json` is printed verbatim in a `<script>` element, the import needs its own scope
+                writeln!(output, "    {{")?;
+                writeln!(output, "    #[allow(unused_imports)]")?;
+                writeln!(output, "    use ::nate::details::ScriptKind as _;")?;
+            }
+            if ctx.settings.context.is_some() {
+                writeln!(output, "    ::nate::details::RenderContext::render_ctx(")?;
+                writeln!(output, "        &{},", WrappedValue(&value, wrap, ctx))?;
+                writeln!(output, "        ctx,")?;
+            } else {
+                writeln!(output, "    ::nate::details::Render::render(")?;
+                writeln!(output, "        &{},", WrappedValue(&value, wrap, ctx))?;
+            }
+            writeln!(output, "        &mut output,")?;
+            writeln!(output, "    )?;")?;
+            if in_script {
+                writeln!(output, "    }}")?;
+            }
+        }
+        write_sections(
+            &mut output,
+            ctx.settings.escape,
+            block_index,
+            start,
+            &blocks[start..],
+        )?;
+
+        writeln!(output, "}}")?;
+    }
+
+    Ok(())
+}
+
+/// The path of a filter function: filters of `nate::filters` are called by their full path, all
+/// other filters are looked up in the scope of the template
+///
+/// `nate` knows its filters, and which of them are enabled, so it resolves the name.
+struct FilterPath<'a>(&'a Filter);
+
+impl fmt::Display for FilterPath<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "::nate::__nate_filter!({})", self.0.name.as_str())
+    }
+}
+
+/// `use ::nate::details::{DisplayKind as _, …};` to select how values are wrapped
+///
+/// Only the HTML escaper prints [`RawMarker`](../nate/trait.RawMarker.html) values and nested
+/// templates verbatim. With the other escapers the kinds only select how a value is displayed,
+/// and [`WrappedValue`] escapes the result. Only templates with a render `context` can render
+/// nested templates with a render context. Only templates with a `float` setting format their
+/// floats with `FloatValue`.
+fn write_kind_imports(
+    mut output: impl Write,
+    escape: Escape,
+    context: bool,
+    float: bool,
+) -> Result<(), CompileError> {
+    writeln!(output, "    #[allow(unused_imports)]")?;
+    writeln!(output, "    use ::nate::details::{{")?;
+    writeln!(output, "        DisplayKind as _,")?;
+    if escape.is_html() {
+        writeln!(output, "        {} as _,", escape.kind())?;
+    }
+    writeln!(output, "        FloatKind as _,")?;
+    if float {
+        writeln!(output, "        FloatValueKind as _,")?;
+    }
+    writeln!(output, "        IntKind as _,")?;
+    if float {
+        writeln!(output, "        OtherValueKind as _,")?;
+    }
+    writeln!(output, "        RawKind as _,")?;
+    if escape.is_html() {
+        writeln!(output, "        RenderKind as _,")?;
+    }
+    writeln!(output, "        RenderRawKind as _,")?;
+    if context {
+        writeln!(output, "        RenderWithKind as _,")?;
+    }
+    writeln!(output, "    }};")?;
+    Ok(())
+}
+
+/// `(&&&&&EscapeWrapper::new(value)).wrap(value)`
+///
+/// With a `float` setting, `(&&FloatValue::new(value)).format(value, &_NATE_FLOAT)` formats the
+/// floats in value blocks first.
+/// With another escaper than the HTML escaper, the wrapped value is escaped with it.
+struct WrappedValue<'a>(&'a str, &'a str, &'a Context);
+
+impl fmt::Display for WrappedValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let WrappedValue(value, wrap, ctx) = *self;
+        let escape = ctx.settings.escape;
+        let (prefix, suffix) = match wrap {
+            "wrap" if !escape.is_html() => (format!("::nate::details::{}(", escape.wrapper()), ")"),
+            _ => (String::new(), ""),
+        };
+        match (&ctx.settings.float, wrap) {
+            (Some(_), "wrap") => write!(
+                f,
+                "{prefix}match &(&&::nate::details::FloatValue::new({value}))\
+                    .format({value}, &_NATE_FLOAT) {{ \
+                    _nate_float => (&&&&&::nate::details::EscapeWrapper::new(_nate_float))\
+                    .{wrap}(_nate_float) }}{suffix}",
+                prefix = prefix,
+                value = value,
+                wrap = wrap,
+                suffix = suffix,
+            ),
+            _ => write!(
+                f,
+                "{prefix}(&&&&&::nate::details::EscapeWrapper::new({value})).{wrap}({value}){suffix}",
+                prefix = prefix,
+                value = value,
+                wrap = wrap,
+                suffix = suffix,
+            ),
+        }
+    }
+}
+
+/// Generate the code of a `{< t "message-id" … >}` or `{< _ "Message" … >}` block
+#[cfg(not(any(feature = "fluent", feature = "gettext")))]
+fn translate(kind: Translation, span: SpanInput, _: &mut Context) -> Result<String, CompileError> {
+    Err(CompileError::Template(span, missing_feature(kind)))
+}
+
+/// Generate the code of a `{< t "message-id" … >}` or `{< _ "Message" … >}` block
+#[cfg(any(feature = "fluent", feature = "gettext"))]
+fn translate(
+    kind: Translation,
+    span: SpanInput,
+    ctx: &mut Context,
+) -> Result<String, CompileError> {
+    let message = match syn::parse_str::<Message>(span.as_str()) {
+        Ok(message) => message,
+        Err(err) => {
+            let msg = format!("could not parse translated message: {}", err);
+            return Err(CompileError::Template(span, msg));
+        },
+    };
+
+    let checked = match kind {
+        #[cfg(feature = "fluent")]
+        Translation::Fluent => {
+            if ctx.messages.is_none() {
+                let dir = ctx.settings.fluent.as_deref().unwrap_or("locales");
+                let dir = ctx.root.join(dir);
+                ctx.messages = Some(crate::fluent::Messages::load(&dir, ctx)?);
+            }
+            match (&ctx.messages, &message.plural) {
+                (_, Some(_)) => Err("a Fluent message selects its plural form itself".to_owned()),
+                (Some(messages), None) => messages.check(&message),
+                (None, None) => Ok(()),
+            }
+        },
+        #[cfg(feature = "gettext")]
+        Translation::Gettext => crate::gettext::check(&message).map(|()| {
+            let path = span
+                .get_shared()
+                .as_deref()
+                .unwrap_or_else(|| Path::new(""));
+            let reference = format!(
+                "{}:{}",
+                crate::gettext::reference_path(&ctx.root, path),
+                span.location_line(),
+            );
+            ctx.texts.add(&message, reference);
+        }),
+        #[allow(unreachable_patterns)]
+        kind => Err(missing_feature(kind)),
+    };
+    if let Err(msg) = checked {
+        return Err(CompileError::Template(span, msg));
+    }
+
+    let mut output = String::new();
+    writeln!(output, "{{")?;
+    writeln!(output, "/* {} */", AddrAnnotation(&span))?;
+    if !message.args.is_empty() {
+        let float = ctx.settings.float.is_some();
+        write_kind_imports(&mut output, ctx.settings.escape, false, float)?;
+        writeln!(output, "    let (")?;
+        for index in 0..message.args.len() {
+            writeln!(output, "        _nate_arg_{},", index)?;
+        }
+        writeln!(output, "    ) = (")?;
+        for arg in &message.args {
+            let value = &arg.value;
+            writeln!(output, "        &({}),", quote!(#value))?;
+        }
+        writeln!(output, "    );")?;
+    }
+    writeln!(output, "    ::nate::details::write_message(")?;
+    writeln!(output, "        &mut output,")?;
+    match (kind, &message.plural) {
+        (Translation::Fluent, _) => writeln!(
+            output,
+            "        &::nate::i18n::Message::fluent({:?}, &[",
+            message.id.value(),
+        )?,
+        (Translation::Gettext, plural) => writeln!(
+            output,
+            "        &::nate::i18n::Message::gettext({:?}, {:?}, &[",
+            message.id.value(),
+            plural.as_ref().map(|plural| plural.value()),
+        )?,
+    }
+    for (index, arg) in message.args.iter().enumerate() {
+        let value = format!("_nate_arg_{}", index);
+        writeln!(output, "            ::nate::i18n::Argument::new(")?;
+        writeln!(output, "                {:?},", arg.name.to_string())?;
+        writeln!(
+            output,
+            "                &::nate::details::RenderDisplay(&{}),",
+            WrappedValue(&value, "wrap", ctx),
+        )?;
+        writeln!(
+            output,
+            "                (&&::nate::details::EscapeWrapper::new({})).is_number(),",
+            value,
+        )?;
+        writeln!(output, "            ),")?;
+    }
+    writeln!(output, "        ]),")?;
+    writeln!(output, "    )?;")?;
+    writeln!(output, "}}")?;
+    Ok(output)
+}
+
+fn missing_feature(kind: Translation) -> String {
+    let feature = match kind {
+        Translation::Fluent => "fluent",
+        Translation::Gettext => "gettext",
+    };
+    format!(
+        "translated messages need the feature `{}` of `nate`",
+        feature
+    )
+}
+
+/// The filter is `date("…")` with a string literal as format description
+fn is_date_format(name: &str, args: &str) -> bool {
+    name == "date" && syn::parse_str::<syn::LitStr>(args).is_ok()
+}
+
+/// Write the data sections `blocks[offset..]` with a single `write!(…)`
+fn write_sections(
+    mut output: impl Write,
+    escape: Escape,
+    block_index: usize,
+    offset: usize,
+    blocks: &[DataSection],
+) -> Result<(), CompileError> {
+    use DataSection::{Data, Debug, Escaped, Raw, Verbose};
+
+    if blocks.iter().all(|data| matches!(data, Data(_))) {
+        if !blocks.is_empty() {
+            let s = blocks
+                .iter()
+                .filter_map(|data| match data {
+                    Data(s) => Some(s.as_str()),
+                    _ => None,
+                })
+                .collect::<String>();
+            writeln!(output, "    <_ as ::nate::WriteAny>::write_str(")?;
+            writeln!(output, "        &mut output,")?;
+            writeln!(output, "        {:#?},", s)?;
+            writeln!(output, "    )?;")?;
+        }
+        return Ok(());
+    }
+
+    writeln!(output, "    <_ as ::nate::WriteAny>::write_fmt(")?;
+    writeln!(output, "        &mut output,")?;
+    writeln!(output, "        ::nate::details::core::format_args!(")?;
+    write!(output, "            \"")?;
+    for (data_index, data) in blocks.iter().enumerate() {
+        let data_index = data_index + offset;
+        match data {
+            Data(s) => {
+                let s = format!("{:#?}", s.as_str())
+                    .replace('{', "{{")
+                    .replace('}', "}}");
+                write!(output, "{}", &s[1..s.len() - 1])?;
+            },
+            Raw(Value { spec: None, .. }) | Escaped(_) => write!(
+                output,
+                "{{_nate_{block}_{data}}}",
+                block = block_index,
+                data = data_index
+            )?,
+            Raw(Value {
+                spec: Some(spec), ..
+            }) => write!(
+                output,
+                "{{_nate_{block}_{data}:{spec}}}",
+                block = block_index,
+                data = data_index,
+                spec = FormatSpec(spec),
+            )?,
+            Debug(_) => write!(
+                output,
+                "{{_nate_{block}_{data}:?}}",
+                block = block_index,
+                data = data_index
+            )?,
+            Verbose(_) => write!(
+                output,
+                "{{_nate_{block}_{data}:#?}}",
+                block = block_index,
+                data = data_index
+            )?,
+        }
+    }
+    writeln!(output, "\",")?;
+    for (data_index, data) in blocks.iter().enumerate() {
+        let data_index = data_index + offset;
+        match data {
+            Data(_) => {},
+            // The kind of the value selects if the formatted value is escaped.
+            Escaped(Value {
+                spec: Some(spec),
+                escape: None,
+                ..
+            }) if escape.is_html() => writeln!(
+                output,
+                "            _nate_{block}_{data} = \
+                    (&&&&::nate::details::EscapeWrapper::new(_nate_{block}_{data})).wrap_fmt(\
+                        ::nate::details::core::format_args!(\"{{:{spec}}}\", _nate_{block}_{data})\
+                    ),",
+                block = block_index,
+                data = data_index,
+                spec = FormatSpec(spec),
+            )?,
+            Escaped(Value {
+                spec: Some(spec),
+                escape: block_escape,
+                ..
+            }) => writeln!(
+                output,
+                "            _nate_{block}_{data} = ::nate::details::{escape}(\
+                    ::nate::details::core::format_args!(\"{{:{spec}}}\", _nate_{block}_{data})\
+                ),",
+                escape = block_escape.unwrap_or(escape).wrapper(),
+                block = block_index,
+                data = data_index,
+                spec = FormatSpec(spec),
+            )?,
+            Escaped(Value {
+                spec: None,
+                escape: Some(block_escape),
+                ..
+            }) => writeln!(
+                output,
+                "            _nate_{block}_{data} = ::nate::details::{escape}(_nate_{block}_{data}),",
+                escape = block_escape.wrapper(),
+                block = block_index,
+                data = data_index,
+            )?,
+            Debug(_) | Verbose(_) => writeln!(
+                output,
+                "            _nate_{block}_{data} = ::nate::details::{escape}(_nate_{block}_{data}),",
+                escape = escape.wrapper(),
+                block = block_index,
+                data = data_index,
+            )?,
+            Raw(_) | Escaped(_) => writeln!(
+                output,
+                "            _nate_{block}_{data} = _nate_{block}_{data},",
+                block = block_index,
+                data = data_index,
+            )?,
+        }
+    }
+    writeln!(output, "        ),")?;
+    writeln!(output, "    )?;")?;
+    Ok(())
+}
+
+fn parse(path: PathBuf, i: String, ctx: &mut Context) -> Result<Vec<ParsedData>, CompileError> {
+    let mut output = Vec::new();
+    parse_into(path, i, &mut output, ctx)?;
+    Ok(output)
+}
+
+fn parse_into(
+    path: PathBuf,
+    i: String,
+    accu: &mut Vec<ParsedData>,
+    ctx: &mut Context,
+) -> Result<(), CompileError> {
+    let span = SpanInput::new_with_shared(i, Some(path.into()));
+    let path = span.get_rc();
+    let path = path.1.as_ref().unwrap().as_ref();
+
+    let s = SpanInput::new(format!(
+        "\
+{{\n\
+const _: &[::nate::details::core::primitive::u8] = \
+::nate::details::core::include_bytes!({:?});",
+        path
+    ));
+    match accu.last_mut() {
+        Some(ParsedData::Code(blocks)) => blocks.push(s),
+        _ => accu.push(ParsedData::Code(vec![s])),
+    }
+
+    let blocks = input_into_blocks(
+        span,
+        ctx.settings.strip,
+        ctx.settings.line_statement.clone(),
+    );
+    // With a minifier, adjacent data sections are minified together.
+    let mut text = String::new();
+    for block in blocks {
+        let block = block?;
+        if !matches!(block, Block::Comment | Block::Data(DataSection::Data(_))) {
+            flush_text(&mut text, accu, ctx);
+        }
+        match block {
+            Block::Comment => {},
+            Block::Code(s) => match accu.last_mut() {
+                Some(ParsedData::Code(blocks)) => blocks.push(s),
+                _ => accu.push(ParsedData::Code(vec![s])),
+            },
+            Block::Data(DataSection::Data(s)) if ctx.minifier.is_some() => {
+                text.push_str(s.as_str());
+            },
+            Block::Data(DataSection::Data(s)) => push_text(s, accu, ctx),
+            Block::Data(DataSection::Escaped(mut value)) => {
+                value.in_script = ctx.settings.escape.is_html() && ctx.raw_text.is_script();
+                push_data(vec![DataSection::Escaped(value)], accu);
+            },
+            Block::Data(data) => push_data(vec![data], accu),
+            Block::Translate(kind, message) => {
+                let s = SpanInput::new(translate(kind, message, ctx)?);
+                match accu.last_mut() {
+                    Some(ParsedData::Code(blocks)) => blocks.push(s),
+                    _ => accu.push(ParsedData::Code(vec![s])),
+                }
+            },
+            Block::Include(include) => {
+                let include_path = include_path(&ctx.root, path, include.as_str());
+                let buf = ctx.load_file(&include_path)?;
+                parse_into(include_path, buf, accu, ctx)?;
+            },
+        }
+    }
+
+    flush_text(&mut text, accu, ctx);
+
+    let s = SpanInput::new("}");
+    match accu.last_mut() {
+        Some(ParsedData::Code(blocks)) => blocks.push(s),
+        _ => accu.push(ParsedData::Code(vec![s])),
+    }
+
+    Ok(())
+}
+
+/// The path of the file that an `{< include >}` block in the template file `path` includes
+///
+/// If the included path starts with "." or "..", it is relative to the including file.
+/// Otherwise it is relative to the project `root`.
+pub fn include_path(root: &Path, path: &Path, include: &str) -> PathBuf {
+    let include = include.trim();
+    match Path::new(include).iter().next() {
+        Some(d) if d.eq(".") || d.eq("..") => path.parent().unwrap_or(path).join(include),
+        _ => root.join(include),
+    }
+}
+
+/// Minify and push the collected `text`
+fn flush_text(text: &mut String, accu: &mut Vec<ParsedData>, ctx: &mut Context) {
+    if let (false, Some(minifier)) = (text.is_empty(), &mut ctx.minifier) {
+        let s = SpanInput::new(minifier.minify(text));
+        text.clear();
+        push_text(s, accu, ctx);
+    }
+}
+
+/// Push a data section, and add nonces to its tags if needed
+fn push_text(s: SpanInput, accu: &mut Vec<ParsedData>, ctx: &mut Context) {
+    let attrs = ctx.raw_text.scan(s.as_str());
+    match &ctx.settings.nonce {
+        _ if s.is_empty() => {},
+        Some(nonce) => push_data(ctx.nonce.add(s, &attrs, nonce), accu),
+        None => push_data(vec![DataSection::Data(s)], accu),
+    }
+}
+
+fn push_data(data: Vec<DataSection>, accu: &mut Vec<ParsedData>) {
+    match accu.last_mut() {
+        Some(ParsedData::Data(blocks)) => blocks.extend(data),
+        _ => accu.push(ParsedData::Data(data)),
+    }
+}
+
+/// A format specifier, escaped to be used inside a string literal
+struct FormatSpec<'a>(&'a SpanInput);
+
+impl fmt::Display for FormatSpec<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self.0.as_str());
+        f.write_str(&s[1..s.len() - 1])
+    }
+}
+
+struct AddrAnnotation<'a>(&'a SpanInput);
+
+impl fmt::Display for AddrAnnotation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match self.0.get_shared() {
+            Some(path) => path.as_os_str(),
+            None => return Ok(()),
+        };
+        write!(
+            f,
+            r"#[::nate::addr(path={path:?}, offset={offset:?}, row={row:?}, col={col:?})]",
+            path = path,
+            offset = self.0.location_offset(),
+            row = self.0.location_line(),
+            col = self.0.naive_get_utf8_column(),
+        )
+    }
+}
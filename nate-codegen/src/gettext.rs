@@ -0,0 +1,292 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::translate::Message;
+
+/// The texts of the `{< _ "Message" … >}` blocks of a template, to be extracted into a `.pot` file
+#[derive(Debug, Default)]
+pub(crate) struct Texts {
+    texts: Vec<Text>,
+    /// The files of the template, as used in the references of the texts
+    files: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Text {
+    id: String,
+    plural: Option<String>,
+    references: Vec<String>,
+    brace_format: bool,
+}
+
+/// Check that every placeholder `{name}` of `message` has an argument, and that every argument
+/// is used
+pub(crate) fn check(message: &Message) -> Result<(), String> {
+    let id = message.id.value();
+    let plural = message.plural.as_ref().map(|plural| plural.value());
+    let mut placeholders = placeholders(&id);
+    if let Some(plural) = &plural {
+        placeholders.extend(self::placeholders(plural));
+        if !message.args.iter().any(|arg| arg.name == "count") {
+            return Err(format!(
+                "the plural form of the gettext text {:?} needs the argument `count`",
+                id,
+            ));
+        }
+    }
+
+    for name in &placeholders {
+        if !message.args.iter().any(|arg| arg.name == name) {
+            return Err(format!(
+                "missing argument `{}` of the gettext text {:?}",
+                name, id,
+            ));
+        }
+    }
+    for arg in &message.args {
+        let name = arg.name.to_string();
+        let used = placeholders.contains(&name) || (plural.is_some() && name == "count");
+        if !used {
+            return Err(format!(
+                "the gettext text {:?} does not use the argument `{}`",
+                id, name,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The placeholders `{name}` in `text`
+fn placeholders(text: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut text = text;
+    while let Some(start) = text.find('{') {
+        text = &text[start + 1..];
+        let end = match text.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let name = &text[..end];
+        let mut chars = name.chars();
+        let is_ident = chars
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_ident {
+            result.push(name.to_owned());
+            text = &text[end + 1..];
+        }
+    }
+    result
+}
+
+/// The path of a template file in the references of a `.pot` file, relative to the project root
+pub(crate) fn reference_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+impl Texts {
+    /// Remember that `path` is a file of the template, even if it contains no texts
+    pub(crate) fn add_file(&mut self, root: &Path, path: &Path) {
+        let path = reference_path(root, path);
+        if !self.files.contains(&path) {
+            self.files.push(path);
+        }
+    }
+
+    /// Add the text of `message`, found at `reference`, e.g. `templates/greeting.html:3`
+    pub(crate) fn add(&mut self, message: &Message, reference: String) {
+        let id = message.id.value();
+        let plural = message.plural.as_ref().map(|plural| plural.value());
+        let brace_format = !placeholders(&id).is_empty()
+            || plural
+                .as_deref()
+                .map_or(false, |plural| !placeholders(plural).is_empty());
+        self.insert(Text {
+            id,
+            plural,
+            references: vec![reference],
+            brace_format,
+        });
+    }
+
+    fn insert(&mut self, new: Text) {
+        match self
+            .texts
+            .iter_mut()
+            .find(|text| text.id == new.id && text.plural == new.plural)
+        {
+            Some(text) => {
+                for reference in new.references {
+                    if !text.references.contains(&reference) {
+                        text.references.push(reference);
+                    }
+                }
+            },
+            None => self.texts.push(new),
+        }
+    }
+
+    /// Merge the texts of an existing `.pot` file into the texts of the template
+    ///
+    /// Multiple templates can share a `.pot` file, e.g. a page and its nested templates, which are
+    /// derived separately. The texts of the other templates are kept, while the references into
+    /// the files of this template are replaced. Texts without references are removed.
+    pub(crate) fn merge_pot(&mut self, pot: &str) {
+        for mut text in parse_pot(pot) {
+            text.references.retain(|reference| {
+                let path = reference
+                    .rsplit_once(':')
+                    .map_or(&reference[..], |(path, _)| path);
+                !self.files.iter().any(|file| file == path)
+            });
+            if !text.references.is_empty() {
+                self.insert(text);
+            }
+        }
+        for text in &mut self.texts {
+            text.references
+                .sort_by(|a, b| reference_key(a).cmp(&reference_key(b)));
+        }
+        self.texts
+            .sort_by(|a, b| reference_key(&a.references[0]).cmp(&reference_key(&b.references[0])));
+    }
+
+    /// The content of a `.pot` file
+    ///
+    /// A `.pot` file has no language, so a plural text gets the two forms `msgstr[0]` and
+    /// `msgstr[1]` like in the files of `xgettext`. The translator adds the forms of their language.
+    pub(crate) fn to_pot(&self) -> Result<String, std::fmt::Error> {
+        let mut output = String::new();
+        writeln!(output, "msgid \"\"")?;
+        writeln!(output, "msgstr \"\"")?;
+        writeln!(output, "\"Content-Type: text/plain; charset=UTF-8\\n\"")?;
+        writeln!(output, "\"Content-Transfer-Encoding: 8bit\\n\"")?;
+        for text in &self.texts {
+            writeln!(output)?;
+            for reference in &text.references {
+                writeln!(output, "#: {}", reference)?;
+            }
+            if text.brace_format {
+                writeln!(output, "#, python-brace-format")?;
+            }
+            writeln!(output, "msgid {}", Quoted(&text.id))?;
+            match &text.plural {
+                Some(plural) => {
+                    writeln!(output, "msgid_plural {}", Quoted(plural))?;
+                    writeln!(output, "msgstr[0] \"\"")?;
+                    writeln!(output, "msgstr[1] \"\"")?;
+                },
+                None => writeln!(output, "msgstr \"\"")?,
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// Sort references like `templates/a.html:10` by their path, then by their line number
+fn reference_key(reference: &str) -> (&str, u64, &str) {
+    match reference.rsplit_once(':') {
+        Some((path, line)) => (path, line.parse().unwrap_or(u64::MAX), line),
+        None => (reference, 0, ""),
+    }
+}
+
+/// Read the texts of a `.pot` file, the translations and unknown lines are ignored
+fn parse_pot(pot: &str) -> Vec<Text> {
+    #[derive(Clone, Copy)]
+    enum Field {
+        Id,
+        Plural,
+        Other,
+    }
+
+    let mut texts = Vec::new();
+    let mut text = None::<Text>;
+    let mut field = Field::Other;
+    for line in pot.lines().map(str::trim).chain([""]) {
+        if line.is_empty() {
+            match text.take() {
+                // the header has an empty id
+                Some(text) if !text.id.is_empty() => texts.push(text),
+                _ => {},
+            }
+            continue;
+        }
+
+        let text = text.get_or_insert_with(|| Text {
+            id: String::new(),
+            plural: None,
+            references: Vec::new(),
+            brace_format: false,
+        });
+        if let Some(references) = line.strip_prefix("#:") {
+            text.references
+                .extend(references.split_whitespace().map(str::to_owned));
+        } else if let Some(flags) = line.strip_prefix("#,") {
+            text.brace_format |= flags
+                .split(',')
+                .any(|flag| flag.trim() == "python-brace-format");
+        } else if let Some(s) = line.strip_prefix("msgid_plural ") {
+            text.plural = Some(unquote(s));
+            field = Field::Plural;
+        } else if let Some(s) = line.strip_prefix("msgid ") {
+            text.id = unquote(s);
+            field = Field::Id;
+        } else if line.starts_with('"') {
+            match (field, &mut text.plural) {
+                (Field::Id, _) => text.id.push_str(&unquote(line)),
+                (Field::Plural, Some(plural)) => plural.push_str(&unquote(line)),
+                _ => {},
+            }
+        } else {
+            field = Field::Other;
+        }
+    }
+    texts
+}
+
+/// The content of a string literal in a `.pot` file
+fn unquote(s: &str) -> String {
+    let s = s.trim();
+    let s = s.strip_prefix('"').unwrap_or(s);
+    let s = s.strip_suffix('"').unwrap_or(s);
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some('r') => result.push('\r'),
+                Some(c) => result.push(c),
+                None => {},
+            },
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// A string literal in a `.pot` file
+struct Quoted<'a>(&'a str);
+
+impl std::fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\t' => f.write_str("\\t")?,
+                '\r' => f.write_str("\\r")?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
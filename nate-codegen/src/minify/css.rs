@@ -0,0 +1,133 @@
+/// Whitespaces before these characters can be removed
+const NO_SPACE_BEFORE: &str = "{};,>";
+
+/// Whitespaces after these characters can be removed
+const NO_SPACE_AFTER: &str = "{};,>:";
+
+#[derive(Debug)]
+pub(crate) struct Css {
+    state: State,
+    /// There are whitespaces that were not written yet
+    space: bool,
+    /// The last character that was written, `None` after a value or code block
+    last: Option<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Normal,
+    /// Inside a string that is quoted with the character
+    Quoted(char),
+    /// Inside a comment that is kept
+    Comment,
+}
+
+impl Default for Css {
+    fn default() -> Self {
+        Self {
+            state: State::Normal,
+            space: false,
+            // the start of the file
+            last: Some('{'),
+        }
+    }
+}
+
+impl Css {
+    pub(crate) fn minify(&mut self, s: &str) -> String {
+        let mut output = String::with_capacity(s.len());
+        let mut i = 0;
+        while let Some(c) = s[i..].chars().next() {
+            i = match self.state {
+                State::Normal => self.normal(s, i, c, &mut output),
+                State::Quoted(quote) => copy_quoted(s, i, quote, &mut output, || {
+                    self.state = State::Normal;
+                }),
+                State::Comment => match s[i..].find("*/") {
+                    Some(end) => {
+                        output.push_str(&s[i..i + end + 2]);
+                        self.state = State::Normal;
+                        i + end + 2
+                    },
+                    None => {
+                        output.push_str(&s[i..]);
+                        s.len()
+                    },
+                },
+            };
+            self.last = output.chars().next_back().or(self.last);
+        }
+
+        // The next section follows a value or a code block, which may print anything.
+        if self.state == State::Normal {
+            if self.space && !self.last.map_or(false, |c| NO_SPACE_AFTER.contains(c)) {
+                output.push(' ');
+            }
+            self.space = false;
+            self.last = None;
+        }
+        output
+    }
+
+    fn normal(&mut self, s: &str, i: usize, c: char, output: &mut String) -> usize {
+        let rest = &s[i..];
+        if c.is_ascii_whitespace() {
+            self.space = true;
+            return i + 1;
+        } else if rest.starts_with("/*") && !rest.starts_with("/*!") {
+            if let Some(end) = rest[2..].find("*/") {
+                self.space = true;
+                return i + end + 4;
+            }
+        }
+
+        let needs_space = !NO_SPACE_BEFORE.contains(c)
+            && !self.last.map_or(false, |c| NO_SPACE_AFTER.contains(c));
+        if self.space && needs_space {
+            output.push(' ');
+        }
+        self.space = false;
+
+        if rest.starts_with("/*") {
+            // a comment that is kept, or that contains a value block
+            output.push_str("/*");
+            self.state = State::Comment;
+            i + 2
+        } else if c == '"' || c == '\'' {
+            output.push(c);
+            self.state = State::Quoted(c);
+            i + 1
+        } else {
+            if c == '}' && output.ends_with(';') {
+                let _ = output.pop();
+            }
+            output.push(c);
+            i + c.len_utf8()
+        }
+    }
+}
+
+/// Copy a quoted string up to and including the unescaped `quote`, and call `found` at its end
+pub(super) fn copy_quoted(
+    s: &str,
+    i: usize,
+    quote: char,
+    output: &mut String,
+    found: impl FnOnce(),
+) -> usize {
+    let mut escaped = false;
+    for (pos, c) in s[i..].char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            let end = i + pos + 1;
+            output.push_str(&s[i..end]);
+            found();
+            return end;
+        }
+    }
+    output.push_str(&s[i..]);
+    s.len()
+}
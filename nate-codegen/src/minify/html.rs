@@ -0,0 +1,274 @@
+/// Elements that are rendered as blocks, so whitespaces next to their tags can be removed
+const BLOCKS: &[&str] = &[
+    "!doctype",
+    "address",
+    "article",
+    "aside",
+    "base",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "link",
+    "main",
+    "menu",
+    "meta",
+    "nav",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "ul",
+];
+
+/// Elements whose content is kept verbatim
+const VERBATIM: &[&str] = &["pre", "script", "style", "textarea"];
+
+#[derive(Debug)]
+pub(crate) struct Html {
+    state: State,
+    /// The name of the current tag in lower case
+    tag: String,
+    /// The current tag is a closing tag
+    closing: bool,
+    /// There are whitespaces that were not written yet
+    space: bool,
+    /// The preceding output ended with the tag of a block element, or it is the start of the file
+    after_block: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Text between tags
+    Text,
+    /// Inside a tag, after its name
+    Tag,
+    /// Inside an attribute value that is quoted with the character
+    Quoted(char),
+    /// Inside a comment that is kept
+    Comment,
+    /// Inside the content of a [`VERBATIM`] element
+    Verbatim,
+}
+
+impl Default for Html {
+    fn default() -> Self {
+        Self {
+            state: State::Text,
+            tag: String::new(),
+            closing: false,
+            space: false,
+            after_block: true,
+        }
+    }
+}
+
+impl Html {
+    pub(crate) fn minify(&mut self, s: &str) -> String {
+        let mut output = String::with_capacity(s.len());
+        let mut i = 0;
+        while let Some(c) = s[i..].chars().next() {
+            i = match self.state {
+                State::Text => self.text(s, i, c, &mut output),
+                State::Tag => self.tag(s, i, c, &mut output),
+                State::Quoted(quote) => copy_until(s, i, &quote.to_string(), &mut output, || {
+                    self.state = State::Tag;
+                }),
+                State::Comment => copy_until(s, i, "-->", &mut output, || {
+                    self.state = State::Text;
+                }),
+                State::Verbatim => {
+                    let end = find_ignore_case(&s[i..], &format!("</{}", self.tag));
+                    let end = end.map_or(s.len(), |end| i + end);
+                    output.push_str(&s[i..end]);
+                    if end < s.len() {
+                        self.state = State::Text;
+                        self.after_block = false;
+                    }
+                    end
+                },
+            };
+        }
+
+        // The next section follows a value or a code block, which may print anything.
+        match self.state {
+            State::Text => {
+                self.write_space(false, &mut output);
+                self.after_block = false;
+            },
+            State::Tag if self.space => {
+                output.push(' ');
+                self.space = false;
+            },
+            _ => {},
+        }
+        output
+    }
+
+    fn text(&mut self, s: &str, i: usize, c: char, output: &mut String) -> usize {
+        if c.is_ascii_whitespace() {
+            self.space = true;
+            return i + 1;
+        } else if c != '<' {
+            self.write_space(false, output);
+            output.push(c);
+            self.after_block = false;
+            return i + c.len_utf8();
+        }
+
+        let rest = &s[i..];
+        if rest.starts_with("<!--") && !rest.starts_with("<!--[if") {
+            if let Some(end) = rest.find("-->") {
+                return i + end + 3;
+            }
+        }
+        if rest.starts_with("<!--") {
+            // a conditional comment, or a comment that contains a value block
+            self.write_space(false, output);
+            output.push_str("<!--");
+            self.state = State::Comment;
+            return i + 4;
+        }
+
+        let closing = rest[1..].starts_with('/');
+        let name_start = if closing { 2 } else { 1 };
+        let name_len = rest[name_start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | ':' | '!')))
+            .unwrap_or(rest.len() - name_start);
+        if name_len == 0 {
+            // not a tag, e.g. `a < b`
+            self.write_space(false, output);
+            output.push(c);
+            self.after_block = false;
+            return i + 1;
+        }
+
+        let end = name_start + name_len;
+        self.tag = rest[name_start..end].to_ascii_lowercase();
+        self.closing = closing;
+        self.write_space(BLOCKS.contains(&self.tag.as_str()), output);
+        output.push_str(&rest[..end]);
+        self.state = State::Tag;
+        i + end
+    }
+
+    fn tag(&mut self, s: &str, i: usize, c: char, output: &mut String) -> usize {
+        if c.is_ascii_whitespace() {
+            self.space = !output.ends_with('=');
+            return i + 1;
+        } else if c == '=' {
+            self.space = false;
+            output.push(c);
+            return i + 1;
+        } else if c == '>' {
+            self.space = false;
+            output.push(c);
+            let is_verbatim = VERBATIM.contains(&self.tag.as_str());
+            self.state = if is_verbatim && !self.closing && !output.ends_with("/>") {
+                State::Verbatim
+            } else {
+                State::Text
+            };
+            self.after_block = BLOCKS.contains(&self.tag.as_str());
+            return i + 1;
+        }
+
+        if self.space {
+            output.push(' ');
+            self.space = false;
+        }
+        if !matches!(c, '"' | '\'') {
+            output.push(c);
+            return i + c.len_utf8();
+        }
+
+        let value = &s[i + 1..];
+        match value.find(c) {
+            Some(end) if output.ends_with('=') && is_unquotable(&value[..end]) => {
+                output.push_str(&value[..end]);
+                i + end + 2
+            },
+            Some(end) => {
+                output.push_str(&s[i..i + end + 2]);
+                i + end + 2
+            },
+            None => {
+                // the value contains a value block
+                output.push(c);
+                self.state = State::Quoted(c);
+                i + 1
+            },
+        }
+    }
+
+    /// Write the pending whitespaces, unless they are next to a block element
+    fn write_space(&mut self, before_block: bool, output: &mut String) {
+        if self.space && !self.after_block && !before_block {
+            output.push(' ');
+        }
+        self.space = false;
+    }
+}
+
+/// Copy `s[i..]` into `output` up to and including `end`, and call `found` if `end` was found
+fn copy_until(s: &str, i: usize, end: &str, output: &mut String, found: impl FnOnce()) -> usize {
+    match s[i..].find(end) {
+        Some(pos) => {
+            let pos = i + pos + end.len();
+            output.push_str(&s[i..pos]);
+            found();
+            pos
+        },
+        None => {
+            output.push_str(&s[i..]);
+            s.len()
+        },
+    }
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+/// The attribute value can be written without quotes
+fn is_unquotable(value: &str) -> bool {
+    !value.is_empty()
+        && !value.ends_with('/')
+        && !value
+            .chars()
+            .any(|c| c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`'))
+}
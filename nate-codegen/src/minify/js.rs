@@ -0,0 +1,250 @@
+use super::css::copy_quoted;
+
+/// A line break after these characters does not end a statement, or is already written
+const NO_NEWLINE_AFTER: &str = "{([,;:=?!&|*%<>~^\n";
+
+/// A line break before these characters does not end a statement
+const NO_NEWLINE_BEFORE: &str = "})],;:.?=&|*%<>";
+
+/// After these characters a `/` starts a regular expression instead of a division
+const REGEX_AFTER: &str = "{}([,;:=?!&|*%<>~^+-";
+
+/// After these keywords a `/` starts a regular expression instead of a division
+const REGEX_KEYWORDS: &[&str] = &[
+    "await",
+    "case",
+    "delete",
+    "do",
+    "else",
+    "in",
+    "instanceof",
+    "new",
+    "return",
+    "throw",
+    "typeof",
+    "void",
+    "yield",
+];
+
+#[derive(Debug)]
+pub(crate) struct Js {
+    state: State,
+    /// There are whitespaces that were not written yet
+    space: Space,
+    /// The last character that was written, `None` after a value or code block
+    last: Option<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Normal,
+    /// Inside a string or template literal that is quoted with the character
+    Quoted(char),
+    /// Inside a block comment that is kept
+    Comment,
+    /// Inside a line comment that is kept, because it contains a value or code block
+    LineComment,
+    /// Inside a regular expression
+    Regex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Space {
+    None,
+    Blank,
+    Newline,
+}
+
+impl Default for Js {
+    fn default() -> Self {
+        Self {
+            state: State::Normal,
+            space: Space::None,
+            // the start of the file
+            last: Some(';'),
+        }
+    }
+}
+
+impl Js {
+    pub(crate) fn minify(&mut self, s: &str) -> String {
+        let mut output = String::with_capacity(s.len());
+        let mut i = 0;
+        while let Some(c) = s[i..].chars().next() {
+            i = match self.state {
+                State::Normal => self.normal(s, i, c, &mut output),
+                State::Quoted(quote) => copy_quoted(s, i, quote, &mut output, || {
+                    self.state = State::Normal;
+                }),
+                State::Comment => match s[i..].find("*/") {
+                    Some(end) => {
+                        output.push_str(&s[i..i + end + 2]);
+                        self.state = State::Normal;
+                        i + end + 2
+                    },
+                    None => {
+                        output.push_str(&s[i..]);
+                        s.len()
+                    },
+                },
+                State::LineComment => match s[i..].find('\n') {
+                    Some(end) => {
+                        // the line break ends the comment, so it is always written
+                        output.push_str(&s[i..i + end + 1]);
+                        self.state = State::Normal;
+                        i + end + 1
+                    },
+                    None => {
+                        output.push_str(&s[i..]);
+                        s.len()
+                    },
+                },
+                State::Regex => self.regex(s, i, &mut output),
+            };
+            self.last = output.chars().next_back().or(self.last);
+        }
+
+        // The next section follows a value or a code block, which may print anything.
+        if self.state == State::Normal {
+            match self.space {
+                Space::Newline if !self.no_newline_after() => output.push('\n'),
+                Space::Newline | Space::Blank if self.last.map_or(true, is_word) => {
+                    output.push(' ')
+                },
+                _ => {},
+            }
+            self.space = Space::None;
+            self.last = None;
+        }
+        output
+    }
+
+    fn normal(&mut self, s: &str, i: usize, c: char, output: &mut String) -> usize {
+        let rest = &s[i..];
+        if c.is_ascii_whitespace() {
+            self.space = self.space.max(
+                if c == '\n' {
+                    Space::Newline
+                } else {
+                    Space::Blank
+                },
+            );
+            return i + 1;
+        } else if rest.starts_with("//") {
+            if let Some(end) = rest.find('\n') {
+                self.space = self.space.max(Space::Newline);
+                return i + end;
+            }
+        } else if rest.starts_with("/*") && !rest.starts_with("/*!") {
+            if let Some(end) = rest[2..].find("*/") {
+                let comment = &rest[..end + 4];
+                let space = if comment.contains('\n') {
+                    Space::Newline
+                } else {
+                    Space::Blank
+                };
+                self.space = self.space.max(space);
+                return i + end + 4;
+            }
+        }
+
+        self.write_space(c, output);
+        if rest.starts_with("/*") {
+            // a comment that is kept, or that contains a value block
+            output.push_str("/*");
+            self.state = State::Comment;
+            i + 2
+        } else if rest.starts_with("//") {
+            // a line comment that contains a value block is kept like a block comment
+            output.push_str("//");
+            self.state = State::LineComment;
+            i + 2
+        } else if matches!(c, '"' | '\'' | '`') {
+            output.push(c);
+            self.state = State::Quoted(c);
+            i + 1
+        } else if c == '/' && self.starts_regex(output) {
+            output.push(c);
+            self.state = State::Regex;
+            i + 1
+        } else {
+            output.push(c);
+            i + c.len_utf8()
+        }
+    }
+
+    /// Write the pending whitespaces that are needed before `next`
+    fn write_space(&mut self, next: char, output: &mut String) {
+        // after a value or a code block the preceding character may be anything
+        let last_is_word = self.last.map_or(true, is_word);
+        let space = match self.space {
+            Space::None => return,
+            Space::Newline if !self.no_newline_after() && !NO_NEWLINE_BEFORE.contains(next) => '\n',
+            _ if last_is_word && is_word(next) => ' ',
+            // e.g. `1 .toString()`
+            _ if self.last.map_or(true, |c| c.is_ascii_digit()) && next == '.' => ' ',
+            _ if self.last == Some(next) && matches!(next, '+' | '-' | '/') => ' ',
+            _ => {
+                self.space = Space::None;
+                return;
+            },
+        };
+        output.push(space);
+        self.space = Space::None;
+    }
+
+    fn no_newline_after(&self) -> bool {
+        self.last.map_or(false, |c| NO_NEWLINE_AFTER.contains(c))
+    }
+
+    /// A `/` after `output` starts a regular expression
+    fn starts_regex(&self, output: &str) -> bool {
+        let last = match self.last {
+            Some(last) => last,
+            None => return false,
+        };
+        if output.ends_with("++") || output.ends_with("--") {
+            // a postfix increment or decrement, e.g. `i++ / 2`, because a regular expression
+            // cannot be incremented
+            return false;
+        } else if REGEX_AFTER.contains(last) {
+            return true;
+        }
+        let word_start = output.rfind(|c: char| !is_word(c)).map_or(0, |pos| pos + 1);
+        REGEX_KEYWORDS.contains(&&output[word_start..])
+    }
+
+    /// Copy a regular expression up to and including its end
+    fn regex(&mut self, s: &str, i: usize, output: &mut String) -> usize {
+        let mut escaped = false;
+        let mut class = false;
+        for (pos, c) in s[i..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '[' => class = true,
+                ']' => class = false,
+                '/' if !class => {
+                    let end = i + pos + 1;
+                    output.push_str(&s[i..end]);
+                    self.state = State::Normal;
+                    return end;
+                },
+                '\n' => {
+                    // not a regular expression after all
+                    output.push_str(&s[i..i + pos]);
+                    self.state = State::Normal;
+                    return i + pos;
+                },
+                _ => {},
+            }
+        }
+        output.push_str(&s[i..]);
+        s.len()
+    }
+}
+
+/// The character can be part of an identifier, keyword or number
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '\\') || !c.is_ascii()
+}
@@ -0,0 +1,51 @@
+mod css;
+mod html;
+mod js;
+
+use darling::FromMeta;
+
+use self::css::Css;
+use self::html::Html;
+use self::js::Js;
+
+/// Minification of the data sections of a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub(crate) enum Minify {
+    /// Collapse whitespaces, remove comments and optional quotes of attribute values.
+    Html,
+    /// Collapse whitespaces and remove comments of a stylesheet.
+    Css,
+    /// Collapse whitespaces and remove comments of a script.
+    Js,
+}
+
+impl Minify {
+    pub(crate) fn minifier(self) -> Minifier {
+        match self {
+            Minify::Html => Minifier::Html(Html::default()),
+            Minify::Css => Minifier::Css(Css::default()),
+            Minify::Js => Minifier::Js(Js::default()),
+        }
+    }
+}
+
+/// Minifies the data sections of a template one after another
+///
+/// The minifier keeps its state between the sections, e.g. if a value block is inside a tag.
+/// Values and code blocks are never touched.
+#[derive(Debug)]
+pub(crate) enum Minifier {
+    Html(Html),
+    Css(Css),
+    Js(Js),
+}
+
+impl Minifier {
+    pub(crate) fn minify(&mut self, s: &str) -> String {
+        match self {
+            Minifier::Html(html) => html.minify(s),
+            Minifier::Css(css) => css.minify(s),
+            Minifier::Js(js) => js.minify(s),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::ops::Range;
+
+use nom::Slice;
+
+use crate::escape::Escape;
+use crate::generate::SpanInput;
+use crate::parse::{DataSection, Value};
+
+/// Adds the attribute `nonce="…"` to the `<script>` and `<style>` start tags of a template
+///
+/// A start tag can span multiple data sections, e.g. `<script src="{{ url }}" nonce="…">`, so the
+/// nonce is added at the end of the tag, when all of its attributes are known.
+#[derive(Debug, Default)]
+pub(crate) struct Nonce {
+    /// The attributes of the current start tag in lower case, as seen so far
+    attrs: String,
+}
+
+impl Nonce {
+    /// Add the attribute to the start tags in the data section `s`
+    ///
+    /// `attrs` are the attributes of the start tags, as found by
+    /// [`RawText::scan()`](crate::raw_text::RawText::scan), so a `<script` inside of a script is
+    /// not a tag. The value of the attribute is the expression `nonce`, which is escaped at
+    /// runtime. Tags that already have a nonce are left as they are.
+    pub(crate) fn add(
+        &mut self,
+        s: SpanInput,
+        attrs: &[Range<usize>],
+        nonce: &str,
+    ) -> Vec<DataSection> {
+        let mut result = Vec::new();
+        let mut start = 0;
+        for range in attrs {
+            self.attrs
+                .push_str(&s.as_str()[range.clone()].to_ascii_lowercase());
+            if range.end == s.len() {
+                // the tag continues in the next data section
+                break;
+            }
+
+            let attrs = std::mem::take(&mut self.attrs);
+            if has_nonce(&attrs) {
+                continue;
+            }
+            let end = match is_self_closing(&attrs) && range.start < range.end {
+                true => range.end - 1,
+                false => range.end,
+            };
+            result.push(DataSection::Data(s.slice(start..end)));
+            result.push(DataSection::Data(SpanInput::new(" nonce=\"")));
+            result.push(DataSection::Escaped(Value {
+                expr: SpanInput::new(nonce.to_owned()),
+                filters: Vec::new(),
+                spec: None,
+                escape: Some(Escape::Html),
+                in_script: false,
+            }));
+            result.push(DataSection::Data(SpanInput::new("\"")));
+            start = end;
+        }
+        result.push(DataSection::Data(s.slice(start..)));
+        result
+    }
+}
+
+/// The attributes of a tag contain a nonce, e.g. `type="module" nonce="…"`
+fn has_nonce(attrs: &str) -> bool {
+    attrs.match_indices("nonce").any(|(index, _)| {
+        let before = attrs[..index].chars().next_back();
+        let after = attrs[index + 5..].chars().next();
+        before.map_or(false, |c| c.is_ascii_whitespace() || c == '"' || c == '\'')
+            && after.map_or(true, |c| c == '=' || c == '/' || c.is_ascii_whitespace())
+    })
+}
+
+/// The attributes end with the `/` of a self-closing tag, e.g. `<style/>`, and not with an
+/// unquoted attribute value, e.g. `<script src=/app.js/>`
+fn is_self_closing(attrs: &str) -> bool {
+    match attrs.strip_suffix('/') {
+        Some(attrs) => attrs
+            .chars()
+            .next_back()
+            .map_or(true, |c| c.is_ascii_whitespace() || c == '"' || c == '\''),
+        None => false,
+    }
+}
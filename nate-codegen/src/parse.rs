@@ -0,0 +1,651 @@
+use std::iter::Peekable;
+
+use darling::FromMeta;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until};
+use nom::combinator::{cut, opt, rest};
+use nom::error::ErrorKind;
+use nom::sequence::{pair, preceded};
+use nom::{error_position, IResult, InputTake, Slice};
+
+use crate::compile_error::CompileError;
+use crate::escape::Escape;
+use crate::generate::SpanInput;
+use crate::strip::Strip;
+
+/// Split a template into its blocks
+///
+/// The whitespaces around the blocks are already stripped as selected by their markers and by
+/// `strip`.
+pub fn input_into_blocks(
+    i: SpanInput,
+    strip: Strip,
+    line_statement: Option<String>,
+) -> impl Iterator<Item = Result<Block, CompileError>> {
+    WsBlockIter(BlockIter(Some(i), line_statement).peekable(), strip).filter_map(move |item| {
+        let WsBlock(a, b, z) = match item {
+            Ok(block) => block,
+            Err(err) => return Some(Err(err)),
+        };
+        let b = match b {
+            Block::Data(DataSection::Data(s)) => {
+                Block::Data(DataSection::Data(strip.apply(z.apply_end(a.apply_start(s)))))
+            },
+            b => b,
+        };
+        if b.is_empty() { None } else { Some(Ok(b)) }
+    })
+}
+
+/// A section of the template that writes into the output
+#[derive(Debug, Clone)]
+pub enum DataSection {
+    /// Static text
+    Data(SpanInput),
+    /// A `{{{ raw block }}}`
+    Raw(Value),
+    /// A `{{ value block }}`
+    Escaped(Value),
+    /// A `{{{{ debug block }}}}`
+    Debug(SpanInput),
+    /// A `{{{{{ verbose block }}}}}`
+    Verbose(SpanInput),
+}
+
+/// The content of a `{{ value }}` or `{{{ raw }}}` block
+#[derive(Debug, Clone)]
+pub struct Value {
+    pub(crate) expr: SpanInput,
+    pub(crate) filters: Vec<Filter>,
+    pub(crate) spec: Option<SpanInput>,
+    /// An escaper selected with `| e("…")` that overrides the escaper of the template
+    pub(crate) escape: Option<Escape>,
+    /// The value block is inside of a `<script>` element of an HTML template
+    pub(crate) in_script: bool,
+}
+
+/// A filter in a value block, e.g. `| truncate(20)`
+#[derive(Debug, Clone)]
+pub(crate) struct Filter {
+    pub(crate) name: SpanInput,
+    pub(crate) args: Option<SpanInput>,
+}
+
+impl Value {
+    /// Remove all `safe` filters, and tell if there were any
+    fn take_safe(&mut self) -> bool {
+        let len = self.filters.len();
+        self.filters
+            .retain(|filter| filter.args.is_some() || filter.name.as_str() != "safe");
+        len != self.filters.len()
+    }
+
+    /// Values that are printed verbatim cannot select an escaper
+    fn reject_escape(&self) -> Result<(), SpanInput> {
+        match self.filters.iter().find(|filter| filter.is_escape()) {
+            Some(filter) => Err(filter.name.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// `linebreaks` returns markup, so it has to be the last filter of an escaped value block
+    ///
+    /// Returns the name of the offending filter otherwise.
+    fn check_linebreaks(&self) -> Result<(), SpanInput> {
+        let filters = self.filters.iter().filter(|filter| !filter.is_escape());
+        match filters.rev().skip(1).find(|filter| filter.is_linebreaks()) {
+            Some(filter) => Err(filter.name.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Remove a trailing `e("…")` or `escape("…")` filter, and remember the selected escaper
+    ///
+    /// `e` and `escape` are reserved names. Returns the offending part of the value block if such
+    /// a filter is not the last one, if its argument is not the name of a known escaper, or if the
+    /// value block has a format specifier, too.
+    fn take_escape(&mut self) -> Result<(), SpanInput> {
+        let index = match self.filters.iter().position(Filter::is_escape) {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        let filter = &self.filters[index];
+        let args = match &filter.args {
+            Some(args) if index + 1 == self.filters.len() => args.clone(),
+            _ => return Err(filter.name.clone()),
+        };
+        let name = match args.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(name) => name,
+            None => return Err(args),
+        };
+        let escape = Escape::from_string(name).map_err(|_| args.clone())?;
+        if let Some(spec) = &self.spec {
+            // The width would either pad the escaped output or be escaped itself.
+            return Err(spec.clone());
+        }
+        self.escape = Some(escape);
+        let _ = self.filters.pop();
+        Ok(())
+    }
+}
+
+impl Filter {
+    /// The filter is `e(…)` or `escape(…)`, which selects the escaper of the value block
+    fn is_escape(&self) -> bool {
+        matches!(self.name.as_str(), "e" | "escape")
+    }
+
+    /// The filter is `nate::filters::linebreaks()`, which escapes its value itself
+    fn is_linebreaks(&self) -> bool {
+        self.name.as_str() == "linebreaks"
+    }
+}
+
+/// A block of a template
+#[derive(Debug, Clone)]
+pub enum Block {
+    /// Static text or a value
+    Data(DataSection),
+    /// A `{% code block %}`
+    Code(SpanInput),
+    /// A `{# comment block #}`
+    Comment,
+    /// The path of an `{< include >}` block
+    Include(SpanInput),
+    /// A `{< t "message-id" … >}` or `{< _ "Message" … >}` block
+    Translate(Translation, SpanInput),
+}
+
+/// The kind of a translated message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Translation {
+    /// `{< t "message-id" … >}`, a Fluent message
+    Fluent,
+    /// `{< _ "Message" … >}`, a gettext text
+    Gettext,
+}
+
+/// Whitespace handling at one side of a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Ws {
+    /// No marker: the default of the template applies
+    Default,
+    /// `+`: keep all whitespaces
+    Keep,
+    /// The default of `strip = "blocks"`: like [`Ws::Line`], but horizontal whitespaces are only
+    /// trimmed in front of a block that starts its line, and after a block that ends its line
+    Block,
+    /// `~`: trim horizontal whitespaces, and a single newline after the block
+    Line,
+    /// `-`: trim all whitespaces
+    Trim,
+}
+
+impl Ws {
+    fn from_marker(marker: Option<char>) -> Self {
+        match marker {
+            Some('+') => Ws::Keep,
+            Some('~') => Ws::Line,
+            Some('-') => Ws::Trim,
+            _ => Ws::Default,
+        }
+    }
+
+    /// Trim the start of a data section that follows a block
+    fn apply_start(self, s: SpanInput) -> SpanInput {
+        match self {
+            Ws::Default | Ws::Keep => s,
+            Ws::Line => {
+                let i = s.as_str();
+                let rest = i.trim_start_matches(is_horizontal_ws);
+                let rest = rest
+                    .strip_prefix("\r\n")
+                    .or_else(|| rest.strip_prefix('\n'))
+                    .unwrap_or(rest);
+                s.slice(i.len() - rest.len()..)
+            },
+            Ws::Block => {
+                let i = s.as_str();
+                let rest = i.trim_start_matches(is_horizontal_ws);
+                match rest
+                    .strip_prefix("\r\n")
+                    .or_else(|| rest.strip_prefix('\n'))
+                {
+                    Some(rest) => s.slice(i.len() - rest.len()..),
+                    None => s,
+                }
+            },
+            Ws::Trim => s.trim_start(),
+        }
+    }
+
+    /// Trim the end of a data section that precedes a block
+    fn apply_end(self, s: SpanInput) -> SpanInput {
+        match self {
+            Ws::Default | Ws::Keep => s,
+            Ws::Line => s.slice(..s.as_str().trim_end_matches(is_horizontal_ws).len()),
+            Ws::Block => {
+                let end = s.as_str().trim_end_matches(is_horizontal_ws).len();
+                let source = s.get_source();
+                let line_start = &source.as_str()[..s.location_offset() + end];
+                if line_start.is_empty() || line_start.ends_with('\n') {
+                    s.slice(..end)
+                } else {
+                    s
+                }
+            },
+            Ws::Trim => s.trim_end(),
+        }
+    }
+}
+
+fn is_horizontal_ws(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+#[derive(Debug)]
+struct WsBlock(Ws, Block, Ws);
+
+impl WsBlock {
+    /// Replace [`Ws::Default`] with the whitespace handling selected by `strip`
+    fn resolve(&mut self, strip: Strip) {
+        let default = match (&self.1, strip) {
+            (Block::Code(_) | Block::Comment, Strip::Blocks) => Ws::Block,
+            _ => Ws::Keep,
+        };
+        for ws in [&mut self.0, &mut self.2] {
+            if *ws == Ws::Default {
+                *ws = default;
+            }
+        }
+    }
+}
+
+impl Block {
+    fn is_empty(&self) -> bool {
+        match self {
+            Block::Comment => true,
+            Block::Code(s) | Block::Data(DataSection::Data(s)) => s.is_empty(),
+            Block::Data(_) | Block::Include(_) | Block::Translate(..) => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BlockIter(Option<SpanInput>, Option<String>);
+
+impl Iterator for BlockIter {
+    type Item = Result<WsBlock, CompileError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.0.take()?;
+        if i.is_empty() {
+            None
+        } else {
+            match parse_ws_block(i, self.1.as_deref()) {
+                Ok((i, block)) => {
+                    self.0 = Some(i);
+                    Some(Ok(block))
+                },
+                Err(err) => Some(Err(CompileError::Nom(err))),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WsBlockIter(Peekable<BlockIter>, Strip);
+
+impl Iterator for WsBlockIter {
+    type Item = Result<WsBlock, CompileError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let strip = self.1;
+        match (self.0.next()?, self.0.peek_mut()) {
+            (Ok(mut cur), Some(Ok(next))) => {
+                cur.resolve(strip);
+                next.resolve(strip);
+                let trim = Ord::max(cur.2, next.0);
+                cur.2 = trim;
+                next.0 = trim;
+                Some(Ok(cur))
+            },
+            (Ok(mut cur), _) => {
+                cur.resolve(strip);
+                Some(Ok(cur))
+            },
+            (cur, _) => Some(cur),
+        }
+    }
+}
+
+fn fail_if_empty(b: SpanInput) -> Result<SpanInput, nom::Err<nom::error::Error<SpanInput>>> {
+    if b.is_empty() {
+        Err(nom::Err::Failure(error_position!(b, ErrorKind::NonEmpty)))
+    } else {
+        Ok(b)
+    }
+}
+
+fn parse_ws_block(i: SpanInput, line_statement: Option<&str>) -> IResult<SpanInput, WsBlock> {
+    alt((
+        |i| parse_line_statement(i, line_statement),
+        |i| {
+            let (i, (a, b, z)) = parse_block(i, "{%", "%}")?;
+            Ok((i, WsBlock(a, Block::Code(b), z)))
+        },
+        |i| {
+            let (i, (a, _, z)) = parse_block(i, "{#", "#}")?;
+            Ok((i, WsBlock(a, Block::Comment, z)))
+        },
+        |i: SpanInput| {
+            let (next_i, (a, b, z)) = parse_block(i, "{<", ">}")?;
+            let b = fail_if_empty(b)?;
+            let b = match parse_translation(&b) {
+                Some((kind, message)) => Block::Translate(kind, message),
+                None => Block::Include(b),
+            };
+            Ok((next_i, WsBlock(a, b, z)))
+        },
+        |i| parse_data_section(i, "{{{{{", "}}}}}", |b| Ok(DataSection::Verbose(b))),
+        |i| parse_data_section(i, "{{{{", "}}}}", |b| Ok(DataSection::Debug(b))),
+        |i| {
+            parse_data_section(i, "{{{", "}}}", |b| {
+                let value = parse_value(b)?;
+                value.reject_escape()?;
+                Ok(DataSection::Raw(value))
+            })
+        },
+        |i| {
+            parse_data_section(i, "{{", "}}", |b| {
+                let mut value = parse_value(b)?;
+                if value.take_safe() {
+                    value.reject_escape()?;
+                    Ok(DataSection::Raw(value))
+                } else {
+                    value.check_linebreaks()?;
+                    value.take_escape()?;
+                    Ok(DataSection::Escaped(value))
+                }
+            })
+        },
+        |i| parse_data(i, line_statement),
+    ))(i)
+}
+
+/// `{< t "message-id" arg = value, … >}` and `{< _ "Message" arg = value, … >}` are translated
+/// messages, not includes
+fn parse_translation(b: &SpanInput) -> Option<(Translation, SpanInput)> {
+    let kind = match b.chars().next()? {
+        't' => Translation::Fluent,
+        '_' => Translation::Gettext,
+        _ => return None,
+    };
+    let message = &b[1..];
+    if !message.starts_with(char::is_whitespace) || !message.trim_start().starts_with('"') {
+        return None;
+    }
+    Some((kind, b.slice(1..).trim()))
+}
+
+/// A line that starts with the `line_statement` prefix is a code block including its newline
+fn parse_line_statement(i: SpanInput, line_statement: Option<&str>) -> IResult<SpanInput, WsBlock> {
+    let prefix = match line_statement {
+        Some(prefix) if i.get_column() == 1 => prefix,
+        _ => return Err(nom::Err::Error(error_position!(i, ErrorKind::Tag))),
+    };
+    let indent = i.len() - i.trim_start_matches(is_horizontal_ws).len();
+    let (i, _) = tag(prefix)(i.slice(indent..))?;
+    let (i, b) = match i.find('\n') {
+        Some(pos) => {
+            let (i, b) = i.take_split(pos);
+            (i.slice(1..), b)
+        },
+        None => rest(i)?,
+    };
+    Ok((i, WsBlock(Ws::Keep, Block::Code(b.trim()), Ws::Keep)))
+}
+
+fn parse_block(
+    i: SpanInput,
+    start: &'static str,
+    end: &'static str,
+) -> IResult<SpanInput, (Ws, SpanInput, Ws)> {
+    let inner = |i: SpanInput| -> IResult<SpanInput, (SpanInput, Ws)> {
+        let (i, inner) = opt(take_until(end))(i)?;
+        let inner = if let Some(inner) = inner {
+            inner
+        } else {
+            let (i, inner) = rest(i)?;
+            return Ok((i, (inner, Ws::Default)));
+        };
+
+        let (i, _) = i.take_split(end.len());
+        let (inner, trim) = match (*inner).chars().next_back() {
+            // e.g. `{{ value :+}}`: a sign flag, not a whitespace marker
+            Some('-' | '+') if start.starts_with("{{") && ends_with_format_spec(&inner) => {
+                (inner, Ws::Default)
+            },
+            Some(c @ ('-' | '~' | '+')) => (inner.take(inner.len() - 1), Ws::from_marker(Some(c))),
+            _ => (inner, Ws::Default),
+        };
+
+        let inner = inner.trim();
+        Ok((i, (inner, trim)))
+    };
+
+    let marker = alt((tag("-"), tag("~"), tag("+")));
+    let (i, (trim_start, (b, trim_end))) = preceded(tag(start), cut(pair(opt(marker), inner)))(i)?;
+    let trim_start = Ws::from_marker(trim_start.and_then(|m| m.chars().next()));
+    Ok((i, (trim_start, b, trim_end)))
+}
+
+fn parse_data_section(
+    i: SpanInput,
+    start: &'static str,
+    end: &'static str,
+    kind: impl 'static + Fn(SpanInput) -> Result<DataSection, SpanInput>,
+) -> IResult<SpanInput, WsBlock> {
+    let (j, (trim_start, b, trim_end)) = parse_block(i, start, end)?;
+    let b = fail_if_empty(b)?;
+    match kind(b) {
+        Ok(data) => Ok((j, WsBlock(trim_start, Block::Data(data), trim_end))),
+        Err(err) => Err(nom::Err::Failure(error_position!(err, ErrorKind::Verify))),
+    }
+}
+
+/// Split a value block into its expression, its filters, and its format specifier
+///
+/// E.g. `self.name | upper | truncate(20) :>8`. A `|` that is followed by a name starts a filter.
+/// If the format specifier is not well-formed, then it is part of the expression.
+///
+/// Returns the segment that is not a well-formed filter.
+fn parse_value(b: SpanInput) -> Result<Value, SpanInput> {
+    let separators = top_level_separators(b.as_str());
+
+    let mut value = Value {
+        expr: b.clone(),
+        filters: Vec::new(),
+        spec: None,
+        escape: None,
+        in_script: false,
+    };
+    let mut end = b.len();
+    if let Some(&(pos, _)) = separators.iter().rev().find(|&&(_, c)| c == ':') {
+        let spec = b.slice(pos + 1..).trim();
+        if is_format_spec(spec.as_str()) {
+            value.spec = Some(spec);
+            end = pos;
+        }
+    }
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for &(pos, c) in &separators {
+        if c == '|' && pos < end {
+            segments.push(b.slice(start..pos).trim());
+            start = pos + 1;
+        }
+    }
+    segments.push(b.slice(start..end).trim());
+
+    for segment in &segments[1..] {
+        value
+            .filters
+            .push(parse_filter(segment).ok_or_else(|| segment.clone())?);
+    }
+    value.expr = segments[0].clone();
+    if value.expr.is_empty() {
+        value.expr = b;
+        value.filters.clear();
+        value.spec = None;
+    }
+    Ok(value)
+}
+
+/// Parse a filter like `upper`, or `truncate(20)`
+fn parse_filter(segment: &SpanInput) -> Option<Filter> {
+    let s = segment.as_str();
+    let (name, args) = match s.find('(') {
+        Some(pos) if s.ends_with(')') => (
+            segment.slice(..pos).trim(),
+            Some(segment.slice(pos + 1..s.len() - 1).trim()),
+        ),
+        Some(_) => return None,
+        None => (segment.clone(), None),
+    };
+
+    let is_ident = |s: &str| {
+        let mut chars = s.chars();
+        matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+            && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+    };
+    if name.as_str().split("::").all(is_ident) {
+        Some(Filter { name, args })
+    } else {
+        None
+    }
+}
+
+/// Find all single `:` characters, and single `|` characters that are followed by a name, that are
+/// not nested in brackets or literals
+fn top_level_separators(s: &str) -> Vec<(usize, char)> {
+    let mut depth = 0_usize;
+    let mut separators = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            let _ = chars.next();
+                        },
+                        '"' => break,
+                        _ => {},
+                    }
+                }
+            },
+            '\'' => {
+                // a char literal, or a lifetime / label
+                let mut lookahead = chars.clone();
+                match (lookahead.next(), lookahead.next()) {
+                    (Some((_, '\\')), _) => {
+                        let _ = chars.next();
+                        let _ = chars.next();
+                        let _ = chars.by_ref().find(|&(_, c)| c == '\'');
+                    },
+                    (Some(_), Some((_, '\''))) => {
+                        let _ = chars.next();
+                        let _ = chars.next();
+                    },
+                    _ => {},
+                }
+            },
+            ':' | '|' if matches!(chars.peek(), Some(&(_, next)) if next == c) => {
+                let _ = chars.next();
+            },
+            ':' if depth == 0 => separators.push((pos, c)),
+            '|' if depth == 0 => {
+                let next = s[pos + 1..].trim_start().chars().next();
+                if matches!(next, Some(c) if c == '_' || c.is_alphabetic()) {
+                    separators.push((pos, c));
+                }
+            },
+            _ => {},
+        }
+    }
+    separators
+}
+
+/// The content of a value block ends with a format specifier, e.g. `value :+`
+fn ends_with_format_spec(b: &str) -> bool {
+    let separators = top_level_separators(b);
+    match separators.iter().rev().find(|&&(_, c)| c == ':') {
+        Some(&(pos, _)) => is_format_spec(b[pos + 1..].trim_start()),
+        None => false,
+    }
+}
+
+/// Check if `spec` is a valid argument of [`format_args!()`] after the colon
+///
+/// Width and precision must be literal integers.
+fn is_format_spec(spec: &str) -> bool {
+    if spec.is_empty() {
+        return false;
+    }
+
+    // [[fill]align]
+    let mut chars = spec.chars();
+    let s = match (chars.next(), chars.next()) {
+        (Some('{' | '}'), Some('<' | '^' | '>')) => return false,
+        (Some(_), Some('<' | '^' | '>')) => chars.as_str(),
+        (Some('<' | '^' | '>'), _) => &spec[1..],
+        _ => spec,
+    };
+    // [sign]['#']['0']
+    let s = s.strip_prefix(&['+', '-'][..]).unwrap_or(s);
+    let s = s.strip_prefix('#').unwrap_or(s);
+    let s = s.strip_prefix('0').unwrap_or(s);
+    // [width]['.' precision]
+    let s = s.trim_start_matches(|c: char| c.is_ascii_digit());
+    let s = match s.strip_prefix('.') {
+        Some(precision) => {
+            let s = precision.trim_start_matches(|c: char| c.is_ascii_digit());
+            if s.len() == precision.len() {
+                return false;
+            }
+            s
+        },
+        None => s,
+    };
+    // type
+    matches!(
+        s,
+        "" | "?" | "x?" | "X?" | "x" | "X" | "o" | "b" | "e" | "E" | "p"
+    )
+}
+
+fn parse_data(i: SpanInput, line_statement: Option<&str>) -> IResult<SpanInput, WsBlock> {
+    let s = i.as_str();
+
+    // The data section contains at least one character, so we don't get stuck at a lone `{`.
+    let start = s.chars().next().map_or(0, char::len_utf8);
+    let mut end = s[start..].find('{').map_or(s.len(), |pos| start + pos);
+    if let Some(prefix) = line_statement {
+        let mut lines = s[..end].match_indices('\n').map(|(pos, _)| pos + 1);
+        if let Some(pos) = lines.find(|&pos| {
+            s[pos..]
+                .trim_start_matches(is_horizontal_ws)
+                .starts_with(prefix)
+        }) {
+            end = pos;
+        }
+    }
+
+    let (i, b) = i.take_split(end);
+    let b = DataSection::Data(b);
+    Ok((i, WsBlock(Ws::Keep, Block::Data(b), Ws::Keep)))
+}
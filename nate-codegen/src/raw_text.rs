@@ -0,0 +1,90 @@
+use std::ops::Range;
+
+/// The elements whose content is raw text, which is not parsed as HTML
+const ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// Where the data sections of an HTML template are, as seen by the HTML parser of a browser
+///
+/// The content of `<script>` and `<style>` elements is raw text, which only ends at the matching
+/// close tag. A `<script` inside of a script, e.g. in a string, is not a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RawText {
+    /// Outside of `<script>` and `<style>` elements
+    Html,
+    /// Inside the start tag of a `<script>` or `<style>` element, e.g. in an attribute
+    StartTag(&'static str),
+    /// Inside the content of a `<script>` or `<style>` element
+    Content(&'static str),
+}
+
+impl Default for RawText {
+    fn default() -> Self {
+        RawText::Html
+    }
+}
+
+impl RawText {
+    /// The content of a `<script>` element
+    pub(crate) fn is_script(self) -> bool {
+        self == RawText::Content("script")
+    }
+
+    /// Advance the state over `text`
+    ///
+    /// Returns the attributes of the `<script` and `<style` start tags in `text`, i.e. the ranges
+    /// between the tag names and the closing `>`. A start tag that began in the preceding text, or
+    /// that continues in the following text, is cut at the start or end of `text`.
+    pub(crate) fn scan(&mut self, text: &str) -> Vec<Range<usize>> {
+        let lower = text.to_ascii_lowercase();
+        let mut result = Vec::new();
+        let mut offset = 0;
+        loop {
+            match *self {
+                RawText::Html => {
+                    let start = match lower[offset..].find('<') {
+                        Some(start) => offset + start + 1,
+                        None => break,
+                    };
+                    offset = start;
+                    if let Some(name) = ELEMENTS.iter().find(|name| is_tag(&lower[start..], name)) {
+                        offset += name.len();
+                        *self = RawText::StartTag(name);
+                    }
+                },
+                RawText::StartTag(name) => match lower[offset..].find('>') {
+                    Some(end) => {
+                        result.push(offset..offset + end);
+                        offset += end + 1;
+                        *self = RawText::Content(name);
+                    },
+                    None => {
+                        result.push(offset..text.len());
+                        break;
+                    },
+                },
+                RawText::Content(name) => {
+                    let end = lower[offset..]
+                        .match_indices("</")
+                        .find(|&(index, _)| is_tag(&lower[offset + index + 2..], name));
+                    match end {
+                        Some((end, _)) => {
+                            offset += end + 2 + name.len();
+                            *self = RawText::Html;
+                        },
+                        None => break,
+                    }
+                },
+            }
+        }
+        result
+    }
+}
+
+/// `text` starts with the tag name `name`, e.g. `script>` or `script type="module"`
+fn is_tag(text: &str, name: &str) -> bool {
+    text.starts_with(name)
+        && text[name.len()..]
+            .chars()
+            .next()
+            .map_or(true, |c| c.is_ascii_whitespace() || c == '>' || c == '/')
+}
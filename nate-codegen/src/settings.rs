@@ -0,0 +1,113 @@
+use std::convert::TryInto;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use blake2::{Blake2s256, Digest};
+use darling::FromDeriveInput;
+
+use crate::compile_error::{CompileError, IoOp};
+use crate::escape::Escape;
+use crate::float::Float;
+#[cfg(feature = "fluent")]
+use crate::fluent;
+#[cfg(feature = "gettext")]
+use crate::gettext;
+use crate::minify::{Minifier, Minify};
+use crate::nonce::Nonce;
+use crate::raw_text::RawText;
+use crate::strip::Strip;
+
+/// The arguments of `#[template(…)]`
+#[derive(Debug, Default, FromDeriveInput)]
+#[darling(attributes(template))]
+pub(crate) struct Settings {
+    pub(crate) path: String,
+    #[darling(default)]
+    pub(crate) generated: Option<String>,
+    #[darling(default)]
+    pub(crate) strip: Strip,
+    #[darling(default)]
+    pub(crate) line_statement: Option<String>,
+    #[darling(default)]
+    pub(crate) escape: Escape,
+    #[darling(default)]
+    pub(crate) float: Option<Float>,
+    #[darling(default)]
+    pub(crate) context: Option<String>,
+    #[darling(default)]
+    pub(crate) nonce: Option<String>,
+    #[darling(default)]
+    pub(crate) minify: Option<Minify>,
+    #[darling(default)]
+    #[cfg_attr(not(feature = "fluent"), allow(dead_code))]
+    pub(crate) fluent: Option<String>,
+    #[darling(default)]
+    pub(crate) pot: Option<String>,
+}
+
+/// The state of the code generation of a template
+#[derive(Debug, Default)]
+pub struct Context {
+    pub(crate) settings: Settings,
+    /// The project root that the paths of the template are relative to
+    pub(crate) root: PathBuf,
+    pub(crate) strings_hash: Blake2s256,
+    pub(crate) minifier: Option<Minifier>,
+    /// Where the generated code is in an HTML template
+    pub(crate) raw_text: RawText,
+    /// The state of adding the attribute `#[template(nonce = "…")]` to start tags
+    pub(crate) nonce: Nonce,
+    #[cfg(feature = "fluent")]
+    pub(crate) messages: Option<fluent::Messages>,
+    #[cfg(feature = "gettext")]
+    pub(crate) texts: gettext::Texts,
+}
+
+impl Context {
+    /// The path of the debug output `#[template(generated = "…")]`, if any
+    pub fn generated(&self) -> Option<&str> {
+        self.settings.generated.as_deref()
+    }
+
+    /// The hash of the files that were loaded for the template
+    pub fn strings_hash(&self) -> &Blake2s256 {
+        &self.strings_hash
+    }
+
+    /// The path of the `.pot` file `#[template(pot = "…")]`, if any
+    #[cfg(feature = "gettext")]
+    pub fn pot(&self) -> Option<&str> {
+        self.settings.pot.as_deref()
+    }
+
+    /// The texts of the template as a `.pot` file, merged into the existing `.pot` file `old`
+    #[cfg(feature = "gettext")]
+    pub fn to_pot(&mut self, old: Option<&str>) -> Result<String, CompileError> {
+        if let Some(old) = old {
+            self.texts.merge_pot(old);
+        }
+        Ok(self.texts.to_pot()?)
+    }
+
+    pub(crate) fn load_file(&mut self, path: &Path) -> Result<String, CompileError> {
+        let mut f = OpenOptions::new()
+            .read(true)
+            .open(path)
+            .map_err(|err| CompileError::IoError(IoOp::Open, path.to_owned(), err))?;
+        let len = f
+            .metadata()
+            .map_err(|err| CompileError::IoError(IoOp::Metadata, path.to_owned(), err))?
+            .len();
+        let mut s = String::with_capacity(len.try_into().unwrap_or_default());
+        let _ = f
+            .read_to_string(&mut s)
+            .map_err(|err| CompileError::IoError(IoOp::Read, path.to_owned(), err))?;
+        self.strings_hash.update((s.len() as u128).to_be_bytes());
+        self.strings_hash.update(s.as_bytes());
+        self.strings_hash.update([0xff_u8]);
+        #[cfg(feature = "gettext")]
+        self.texts.add_file(&self.root, path);
+        Ok(s)
+    }
+}
@@ -0,0 +1,92 @@
+use darling::FromMeta;
+use nom::Slice;
+
+use crate::generate::SpanInput;
+
+/// Whitespace handling of the static text of a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum Strip {
+    /// Don't strip any spaces in the input. This is the default.
+    None,
+    /// Remove a single newline at the end of the input.
+    Tail,
+    /// Remove all whitespaces at the front and back all lines, and remove empty lines.
+    Trim,
+    /// Like Trim, but also replace runs of whitespaces with a single space.
+    Eager,
+    /// Remove the indentation in front of code and comment blocks that start their line, and a
+    /// single newline after them. Use `+` to opt-out for a single block.
+    Blocks,
+}
+
+impl Default for Strip {
+    fn default() -> Self {
+        Strip::None
+    }
+}
+
+impl Strip {
+    /// Strip the whitespaces of the data section `s`
+    ///
+    /// Only the static text of the template is changed, code and value blocks are kept as they
+    /// are. The start and end of the data section are only trimmed if they are the start or the
+    /// end of a line in the template file.
+    pub(crate) fn apply(self, s: SpanInput) -> SpanInput {
+        let source = s.get_source();
+        let source = source.as_str();
+        let start = s.location_offset();
+        let end = start + s.len();
+        let at_line_start = start == 0 || source[..start].ends_with('\n');
+        let at_line_end = end == source.len() || source[end..].starts_with('\n');
+        match self {
+            Strip::None | Strip::Blocks => s,
+            Strip::Tail => match s.as_str().strip_suffix('\n') {
+                Some(rest) if end == source.len() => s.slice(..rest.len()),
+                _ => s,
+            },
+            Strip::Trim | Strip::Eager => {
+                let lines = s.as_str().split('\n').collect::<Vec<_>>();
+                let mut stripped = Vec::with_capacity(lines.len());
+                for (index, &line) in lines.iter().enumerate() {
+                    let line_start = index > 0 || at_line_start;
+                    let line_end = index + 1 < lines.len() || at_line_end;
+                    let line = match (line_start, line_end) {
+                        (true, true) => line.trim(),
+                        (true, false) => line.trim_start(),
+                        (false, true) => line.trim_end(),
+                        (false, false) => line,
+                    };
+                    if line_start && line_end && line.is_empty() {
+                        continue;
+                    }
+                    stripped.push(match self {
+                        Strip::Eager => collapse_whitespaces(line),
+                        _ => line.to_owned(),
+                    });
+                }
+                SpanInput::new(stripped.join("\n"))
+            },
+        }
+    }
+}
+
+/// Replace runs of whitespaces with a single space
+fn collapse_whitespaces(line: &str) -> String {
+    let mut collapsed = String::with_capacity(line.len());
+    let mut space = false;
+    for c in line.chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space {
+            collapsed.push(' ');
+            space = false;
+        }
+        collapsed.push(c);
+    }
+    if space {
+        collapsed.push(' ');
+    }
+    collapsed
+}
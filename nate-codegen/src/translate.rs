@@ -0,0 +1,45 @@
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+/// The content of a `{< t "message-id" arg = value, … >}` or
+/// `{< _ "Message" "Plural" arg = value, … >}` block
+#[derive(Debug)]
+pub(crate) struct Message {
+    pub(crate) id: LitStr,
+    pub(crate) plural: Option<LitStr>,
+    pub(crate) args: Vec<Argument>,
+}
+
+/// A named argument `arg = value` of a translated message
+#[derive(Debug)]
+pub(crate) struct Argument {
+    pub(crate) name: Ident,
+    pub(crate) value: Expr,
+}
+
+impl Parse for Message {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let id = input.parse()?;
+        let plural = if input.peek(LitStr) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let args = Punctuated::<Argument, Token![,]>::parse_terminated(input)?;
+        Ok(Self {
+            id,
+            plural,
+            args: args.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
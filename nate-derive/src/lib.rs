@@ -60,26 +60,15 @@
 //! This libary implements the `#![derive(Nate)]` annotation.
 //!
 
-mod compile_error;
-mod generate;
-mod nate_span;
-mod parse;
-mod strip;
-
-use std::convert::TryInto;
-use std::fs::OpenOptions;
-use std::io::Read;
+use std::env::var;
 use std::path::Path;
 
-use blake2::{Blake2s256, Digest};
-use compile_error::IoOp;
-use darling::FromDeriveInput;
+use blake2::digest::FixedOutput;
+use blake2::Digest;
+use nate_codegen::{expand, CompileError, IoOp};
 use proc_macro::TokenStream;
 use quote::quote;
-
-use crate::compile_error::CompileError;
-use crate::generate::generate;
-use crate::strip::Strip;
+use syn::DeriveInput;
 
 /// Implement [`fmt::Display`](core::fmt::Display) for a struct or enum
 ///
@@ -90,6 +79,15 @@ use crate::strip::Strip;
 /// #[template(
 ///     path = "…",
 ///     generated = "…",
+///     strip = "…",
+///     line_statement = "…",
+///     escape = "…",
+///     float(fixed = …, significant = …, nan = "…", infinity = "…", no_exponent),
+///     context = "…",
+///     nonce = "…",
+///     minify = "…",
+///     fluent = "…",
+///     pot = "…",
 /// )]
 /// struct Template { /* … */ }
 /// ```
@@ -100,6 +98,61 @@ use crate::strip::Strip;
 /// The optional debug output path `generated` is relative to the cargo manifest dir.
 /// If supplied the generated code will be written into this file.
 /// An existing file fill be replaced!
+///
+/// The optional argument `strip` selects the whitespace handling of the static text in the
+/// template files: `"none"` (the default) keeps the whitespaces, `"tail"` removes a single newline
+/// at the end of the file, `"trim"` removes the whitespaces at the front and back of every line
+/// and removes empty lines, and `"eager"` additionally replaces runs of whitespaces with a single
+/// space. Code and value blocks are not changed.
+/// With `strip = "blocks"` the indentation in front of code and comment blocks that start their
+/// line is removed, and a single newline after the blocks.
+///
+/// If the optional argument `line_statement` is set, e.g. to `"%"`, then every line that starts
+/// with this prefix is a code block. The newline at the end of the line is removed, too.
+///
+/// The optional argument `escape` selects the escaper for `{{ value blocks }}`: `"html"` (the
+/// default), `"url"` for query components, `"url_path"` for path segments, `"url_form"` for
+/// `application/x-www-form-urlencoded` data, `"js"` for JavaScript strings, `"css"` for CSS
+/// strings, or `"css_ident"` for CSS identifiers.
+///
+/// The optional argument `float(…)` selects how floats are printed in `{{ value blocks }}`, see
+/// `nate::fmt::FloatFormat`. All options are optional: `fixed = 2` prints exactly two fraction
+/// digits, `significant = 3` prints three significant digits, `nan = "…"` and `infinity = "…"`
+/// replace `NaN` and `inf`, and `no_exponent` prints e.g. `1e-7` as `0.0000001`.
+///
+/// The optional argument `context` is the type of the render context, e.g. `"RequestContext"`.
+/// The template can access the context as the variable `ctx`, and has to be rendered with
+/// `RenderWith::render_with()`. The context type may use the lifetimes of the template, e.g.
+/// `context = "RequestContext<'a>"` for a `struct Page<'a>`. Such a template implements
+/// `RenderWith` instead of `RenderInto` and `Display`, because it cannot be rendered without its
+/// context: it can be nested into templates with the same context type in `{{ value blocks }}` of
+/// HTML templates, but it cannot be passed to filters or `to_string()`.
+///
+/// The optional argument `nonce` is an expression, e.g. `"ctx.nonce"`. If supplied, the
+/// attribute `nonce="…"` is added to every `<script>` and `<style>` tag in the template, so
+/// that inline scripts and styles are allowed by a Content Security Policy. The value is
+/// evaluated and escaped at runtime. Tags that already have a `nonce` attribute are kept.
+/// The content of `<script>` and `<style>` elements is skipped until the matching close tag, so
+/// e.g. a string `"<script>"` inside of a script is not a tag.
+///
+/// The optional argument `minify = "html"` minifies the data sections of the template at compile
+/// time: whitespaces are collapsed and removed next to block elements, comments are removed, and
+/// attribute values are unquoted where possible. The content of `<pre>`, `<textarea>`, `<script>`
+/// and `<style>` elements, and values are not touched.
+/// With `minify = "css"` or `minify = "js"` the whitespaces and comments of a stylesheet or a
+/// script are removed where they are not needed. Comments that start with `/*!` are kept,
+/// and so are comments that contain a value or code block.
+///
+/// The optional argument `fluent` is the directory of the `.ftl` files that `{< t "…" >}` blocks
+/// are checked against, relative to the cargo manifest dir. It defaults to `"locales"`.
+/// Translated messages need the feature `fluent`.
+///
+/// The optional argument `pot` is a path relative to the cargo manifest dir. If supplied, the
+/// texts of the `{< _ "…" >}` blocks of the template are extracted into this `.pot` file.
+/// Nested templates are derived on their own, so give them the same `pot` path to extract their
+/// texts into the same file. The texts are merged into an existing file: the texts of other
+/// templates are kept, and the texts of this template are updated. gettext texts need the feature
+/// `gettext`.
 #[proc_macro_derive(Nate, attributes(template))]
 pub fn derive_nate(input: TokenStream) -> TokenStream {
     let err = match generate(input) {
@@ -115,42 +168,43 @@ pub fn derive_nate(input: TokenStream) -> TokenStream {
     ))
 }
 
-#[derive(Debug, Default, FromDeriveInput)]
-#[darling(attributes(template))]
-struct Settings {
-    path: String,
-    #[darling(default)]
-    generated: Option<String>,
-    #[darling(default)]
-    #[allow(unused)] // TODO
-    strip: Strip,
-}
+fn generate(input: TokenStream) -> Result<TokenStream, CompileError> {
+    let ast: DeriveInput = syn::parse(input)?;
+    let base = var("CARGO_MANIFEST_DIR").unwrap();
+    #[cfg_attr(not(feature = "gettext"), allow(unused_mut))]
+    let (content, mut ctx) = expand(&ast, Path::new(&base))?;
 
-#[derive(Debug, Default)]
-struct Context {
-    settings: Settings,
-    strings_hash: Blake2s256,
+    #[cfg(feature = "gettext")]
+    if let Some(path) = ctx.pot().map(|pot| Path::new(&base).join(pot)) {
+        let old = std::fs::read_to_string(&path).ok();
+        write_if_changed(&path, &ctx.to_pot(old.as_deref())?)?;
+    }
+    let output = if let Some(output) = ctx.generated() {
+        Path::new(&base).join(output)
+    } else {
+        let mut strings_hash = ctx.strings_hash().clone();
+        strings_hash.update(content.as_bytes());
+        let out_dir = AsRef::<Path>::as_ref(&env!("NATE_DERIVE_OUTDIR"));
+        let mut temp_name = hex::encode(strings_hash.finalize_fixed());
+        temp_name.push_str(".rs");
+        out_dir.join(temp_name)
+    };
+    write_if_changed(&output, &content)?;
+
+    let output = output.to_str().unwrap();
+    let content = quote! {
+        const _: () = ::core::include!(#output);
+    };
+    Ok(content.into())
 }
 
-impl Context {
-    fn load_file(&mut self, path: &Path) -> Result<String, CompileError> {
-        let mut f = OpenOptions::new()
-            .read(true)
-            .open(path)
-            .map_err(|err| CompileError::IoError(IoOp::Open, path.to_owned(), err))?;
-        let len = f
-            .metadata()
-            .map_err(|err| CompileError::IoError(IoOp::Metadata, path.to_owned(), err))?
-            .len();
-        let mut s = String::with_capacity(len.try_into().unwrap_or_default());
-        let _ = f
-            .read_to_string(&mut s)
-            .map_err(|err| CompileError::IoError(IoOp::Read, path.to_owned(), err))?;
-        self.strings_hash.update((s.len() as u128).to_be_bytes());
-        self.strings_hash.update(s.as_bytes());
-        self.strings_hash.update([0xff_u8]);
-        Ok(s)
+/// Write `content` into the file at `path`, unless it already has this content
+fn write_if_changed(path: &Path, content: &str) -> Result<(), CompileError> {
+    if std::fs::read_to_string(path).map_or(false, |old| old == content) {
+        return Ok(());
     }
+    std::fs::write(path, content)
+        .map_err(|err| CompileError::IoError(IoOp::Write, path.into(), err))
 }
 
 #[doc(hidden)]
@@ -0,0 +1,91 @@
+use std::fmt::{self, Write};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use nate::XmlEscape;
+
+/// The table based implementation of `nate` 0.4, which escapes byte by byte, as a baseline
+struct BytewiseXmlEscape<'a>(&'a str);
+
+struct BytewiseXmlEscapeWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+const MIN_CHAR: u8 = b'"';
+const MAX_CHAR: u8 = b'>';
+const TABLE: [Option<&&str>; (MAX_CHAR - MIN_CHAR + 1) as usize] = {
+    let mut table = [None; (MAX_CHAR - MIN_CHAR + 1) as usize];
+    table[(b'"' - MIN_CHAR) as usize] = Some(&"&#34;");
+    table[(b'&' - MIN_CHAR) as usize] = Some(&"&#38;");
+    table[(b'\'' - MIN_CHAR) as usize] = Some(&"&#39;");
+    table[(b'<' - MIN_CHAR) as usize] = Some(&"&#60;");
+    table[(b'>' - MIN_CHAR) as usize] = Some(&"&#62;");
+    table
+};
+
+impl fmt::Display for BytewiseXmlEscape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        BytewiseXmlEscapeWriter(f).write_str(self.0)
+    }
+}
+
+impl fmt::Write for BytewiseXmlEscapeWriter<'_, '_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        let mut last = 0;
+        for (index, byte) in string.bytes().enumerate() {
+            let escaped = match byte {
+                MIN_CHAR..=MAX_CHAR => TABLE[(byte - MIN_CHAR) as usize],
+                _ => None,
+            };
+            if let Some(escaped) = escaped {
+                self.0.write_str(&string[last..index])?;
+                self.0.write_str(escaped)?;
+                last = index + 1;
+            }
+        }
+        self.0.write_str(&string[last..])
+    }
+}
+
+const PROSE: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod \
+    tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud \
+    exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. ";
+
+const MARKUP: &str = "<p class=\"intro\">Tom &amp; Jerry's <b>\"best\"</b> episodes</p>\n";
+
+fn bench_escape(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xml-escape");
+    let inputs = [
+        ("safe-text", PROSE.repeat(64)),
+        (
+            "mostly-safe",
+            format!("{}{}", PROSE.repeat(8), MARKUP).repeat(8),
+        ),
+        ("markup", MARKUP.repeat(64)),
+        ("short", "Hello, <World>!".to_owned()),
+    ];
+    for (name, input) in &inputs {
+        let input = input.as_str();
+        assert_eq!(
+            XmlEscape(input).to_string(),
+            BytewiseXmlEscape(input).to_string(),
+        );
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_function(format!("chunked/{}", name), |b| {
+            let mut buf = String::with_capacity(2 * input.len());
+            b.iter(|| {
+                buf.clear();
+                write!(buf, "{}", XmlEscape(black_box(input))).unwrap();
+            })
+        });
+        group.bench_function(format!("bytewise/{}", name), |b| {
+            let mut buf = String::with_capacity(2 * input.len());
+            b.iter(|| {
+                buf.clear();
+                write!(buf, "{}", BytewiseXmlEscape(black_box(input))).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_escape);
+criterion_main!(benches);
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nate::{Nate, RawMarker, RenderInto};
+
+/// Nested templates are rendered directly into the output of their parent
+#[derive(Nate)]
+#[template(path = "templates/bench-tree.html")]
+struct Tree {
+    name: String,
+    children: Vec<Tree>,
+}
+
+/// Nested templates are printed using their `Display` implementation
+#[derive(Nate)]
+#[template(path = "templates/bench-tree-display.html")]
+struct TreeDisplay {
+    name: String,
+    children: Vec<TreeDisplay>,
+}
+
+struct ViaDisplay<'a, T>(&'a T);
+
+impl<T: fmt::Display> fmt::Display for ViaDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> RawMarker for ViaDisplay<'_, T> {}
+
+fn tree(depth: usize, width: usize) -> Tree {
+    Tree {
+        name: format!("node <{}>", depth),
+        children: match depth {
+            0 => vec![],
+            _ => (0..width).map(|_| tree(depth - 1, width)).collect(),
+        },
+    }
+}
+
+fn tree_display(depth: usize, width: usize) -> TreeDisplay {
+    TreeDisplay {
+        name: format!("node <{}>", depth),
+        children: match depth {
+            0 => vec![],
+            _ => (0..width).map(|_| tree_display(depth - 1, width)).collect(),
+        },
+    }
+}
+
+fn bench_nested(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nested");
+    for &(depth, width) in &[(3, 3), (8, 3), (32, 1)] {
+        let id = format!("depth={},width={}", depth, width);
+
+        let template = tree(depth, width);
+        let mut expected = String::new();
+        template.render_string(&mut expected).unwrap();
+        group.bench_function(format!("render_into/string/{}", id), |b| {
+            let mut buf = String::with_capacity(expected.len());
+            b.iter(|| {
+                buf.clear();
+                black_box(&template).render_string(&mut buf).unwrap();
+            })
+        });
+        group.bench_function(format!("render_into/io/{}", id), |b| {
+            let mut buf = Vec::with_capacity(expected.len());
+            b.iter(|| {
+                buf.clear();
+                black_box(&template).render_io(&mut buf).unwrap();
+            })
+        });
+
+        let template = tree_display(depth, width);
+        let mut buf = String::new();
+        template.render_string(&mut buf).unwrap();
+        assert_eq!(buf, expected);
+        group.bench_function(format!("display/string/{}", id), |b| {
+            let mut buf = String::with_capacity(expected.len());
+            b.iter(|| {
+                buf.clear();
+                black_box(&template).render_string(&mut buf).unwrap();
+            })
+        });
+        group.bench_function(format!("display/io/{}", id), |b| {
+            let mut buf = Vec::with_capacity(expected.len());
+            b.iter(|| {
+                buf.clear();
+                black_box(&template).render_io(&mut buf).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_nested);
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+welcome-user = Willkommen, <b>{ $user }</b>!
+unread-messages =
+    Sie haben { $count ->
+        [one] eine ungelesene Nachricht
+       *[other] { $count } ungelesene Nachrichten
+    }.
+site-name = NaTE
+footer = © { $year } { site-name }
+contact = Rufen Sie { $phone } an.
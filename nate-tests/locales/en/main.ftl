@@ -0,0 +1,9 @@
+welcome-user = Welcome, <b>{ $user }</b>!
+unread-messages =
+    You have { $count ->
+        [one] one unread message
+       *[other] { $count } unread messages
+    }.
+site-name = NaTE
+footer = © { $year } { site-name }
+contact = Call { $phone }.
@@ -0,0 +1,4 @@
+// by {{ self.a }} here
+foo();
+/* {{ self.a }} */ bar(); // {{ self.a }} =
+baz();
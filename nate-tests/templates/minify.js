@@ -0,0 +1,17 @@
+// greet the user
+const user = "{{ self.user }}";
+let count = {{ self.count }}   ;
+/* a
+   comment */
+function greet(name) {
+    if (count > 0 && /^\w+ \//.test(name)) {
+        return `Hello, ${ name }!   ` + - count;
+    }
+    return name
+        .trim() ;
+}
+/*! keep the license */
+let a = count
+++count
+alert( greet( user ) / 2 ) ;
+count++ / 2; const s = "a  /  b";
@@ -47,3 +47,19 @@ fn five_bottles_of_beer_implicit_braces() -> Result {
     assert_eq!(buf, EXPECTED);
     Ok(())
 }
+
+#[test]
+fn five_bottles_of_beer_line_statements() -> Result {
+    #[derive(Nate)]
+    #[template(
+        path = "templates/99-bottles-line-statements.html",
+        line_statement = "%"
+    )]
+    struct Template {
+        limit: usize,
+    }
+
+    let buf = format!("{}", Template { limit: 5 });
+    assert_eq!(buf.strip_suffix('\n'), Some(EXPECTED));
+    Ok(())
+}
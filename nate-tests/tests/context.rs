@@ -0,0 +1,98 @@
+use std::fmt::Result;
+
+use nate::i18n::{Catalog, Localized};
+use nate::{Nate, RenderWith};
+
+struct RequestContext {
+    user: String,
+}
+
+#[derive(Nate)]
+#[template(path = "templates/context.html", context = "RequestContext")]
+struct Page {
+    title: &'static str,
+    items: Vec<Item>,
+    footer: Footer,
+}
+
+#[derive(Nate)]
+#[template(path = "templates/context-item.html", context = "RequestContext")]
+struct Item(&'static str);
+
+#[derive(Nate)]
+#[template(
+    path = "templates/context-footer.html",
+    context = "crate::RequestContext"
+)]
+struct Footer;
+
+fn page() -> Page {
+    Page {
+        title: "<Orders>",
+        items: vec![Item("a&b"), Item("c")],
+        footer: Footer,
+    }
+}
+
+#[test]
+fn test_context() -> Result {
+    let ctx = RequestContext {
+        user: "<Alice>".to_owned(),
+    };
+    let mut buf = String::new();
+    page().render_with(&ctx, &mut buf)?;
+    assert_eq!(
+        buf,
+        "\
+<h1>&#60;Orders&#62;</h1>
+<p>Hello, &#60;Alice&#62;!</p>
+<li>a&#38;b for &#60;Alice&#62;</li>
+<li>c for &#60;Alice&#62;</li>
+<footer>Logged in as &#60;Alice&#62;</footer>
+
+",
+    );
+    Ok(())
+}
+
+#[test]
+fn test_context_localized() -> Result {
+    let catalog = Catalog::parse(
+        "de",
+        r#"
+msgid "Logged in as {user}"
+msgstr "Angemeldet als {user}"
+"#,
+    )
+    .unwrap();
+    let ctx = RequestContext {
+        user: "Alice".to_owned(),
+    };
+    let mut buf = String::new();
+    Localized::new(&Footer, &catalog).render_with(&ctx, &mut buf)?;
+    assert_eq!(buf, "<footer>Angemeldet als Alice</footer>\n");
+    Ok(())
+}
+
+/// The context can borrow data, e.g. from the request
+struct BorrowedContext<'a> {
+    user: &'a str,
+}
+
+#[derive(Nate)]
+#[template(path = "templates/context-item.html", context = "BorrowedContext<'a>")]
+struct BorrowedItem<'a>(&'a str);
+
+#[test]
+fn test_context_borrowed() {
+    let user = String::from("<Bob>");
+    let ctx = BorrowedContext { user: &user };
+
+    let mut buf = String::new();
+    BorrowedItem("x").render_with_string(&ctx, &mut buf).unwrap();
+    assert_eq!(buf, "<li>x for &#60;Bob&#62;</li>\n");
+
+    let mut buf = Vec::new();
+    BorrowedItem("y").render_with_io(&ctx, &mut buf).unwrap();
+    assert_eq!(buf, b"<li>y for &#60;Bob&#62;</li>\n");
+}
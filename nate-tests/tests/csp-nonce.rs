@@ -0,0 +1,47 @@
+use std::fmt::Result;
+
+use nate::{Nate, RenderWith};
+
+struct RequestContext {
+    nonce: String,
+}
+
+#[derive(Nate)]
+#[template(
+    path = "templates/csp-nonce.html",
+    context = "RequestContext",
+    nonce = "ctx.nonce"
+)]
+struct Page {
+    color: &'static str,
+    module: &'static str,
+}
+
+#[test]
+fn test_csp_nonce() -> Result {
+    let ctx = RequestContext {
+        nonce: "r4nd\"0m".to_owned(),
+    };
+    let template = Page {
+        color: "red",
+        module: "main",
+    };
+    let mut buf = String::new();
+    template.render_with(&ctx, &mut buf)?;
+    assert_eq!(
+        buf,
+        r#"<style nonce="r4nd&#34;0m">body { color: red; }</style>
+<script src="/app.js" nonce="r4nd&#34;0m"></script>
+<SCRIPT type="module" nonce="r4nd&#34;0m">import "/main.js";</SCRIPT>
+<script nonce="static">/* kept */</script>
+<script src="/main.js" nonce="static"></script>
+<style data-color="red" nonce="r4nd&#34;0m"></style>
+<scripts-are-no-tags></scripts-are-no-tags>
+<script nonce="r4nd&#34;0m">document.write("<script src=x>");</script>
+<script nonce="r4nd&#34;0m">var css = "<style>", html = "</scripts>";</script>
+<script nonce="r4nd&#34;0m">/* main <script> */</script>
+<style nonce="r4nd&#34;0m"/>
+"#,
+    );
+    Ok(())
+}
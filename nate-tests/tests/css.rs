@@ -104,3 +104,21 @@ background: lightyellow;
     Ok(())
 }
 */
+
+#[test]
+fn test_css_minify() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/minify.css", minify = "css")]
+    struct Template<'a> {
+        color: &'a str,
+    }
+
+    let tmpl = Template { color: "red" };
+    let mut buf = String::new();
+    write!(buf, "{}", tmpl)?;
+    assert_eq!(
+        buf,
+        r#"#body>p:hover,a::after{color:red;width:calc( 100% - 2px );content:"a  b"}@media screen and (max-width:600px){p{margin :0}}"#,
+    );
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::fmt::{Result, Write};
+
+use nate::fmt::{Date, DateFormat, Relative};
+use nate::Nate;
+use time::macros::{date, datetime};
+
+const UPDATED: DateFormat = DateFormat::new("[hour repr:12 padding:none]:[minute] [period]");
+
+#[test]
+fn test_date_in_template() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/date.html")]
+    struct Template {
+        created: time::OffsetDateTime,
+        updated: chrono::NaiveDateTime,
+        now: time::OffsetDateTime,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        created: datetime!(2022-05-09 13:37:05 -05:30),
+        updated: chrono::NaiveDate::from_ymd_opt(2022, 5, 10)
+            .unwrap()
+            .and_hms_opt(0, 5, 0)
+            .unwrap(),
+        now: datetime!(2022-05-09 22:07:05 UTC),
+    })?;
+    assert_eq!(
+        buf,
+        "<time datetime=\"2022-05-09T13:37:05-05:30\">Mon, 9 May 2022</time> \
+         (3 hours ago, 12:05 AM)\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_date_format() {
+    const FULL: DateFormat = DateFormat::new(
+        "[[[year repr:last_two]] [ordinal] [subsecond] [subsecond digits:3] [unix_timestamp]",
+    );
+    let value = datetime!(2022-05-10 13:37:05.25 UTC);
+    assert_eq!(Date(&value, FULL).to_string(), "[22] 130 25 250 1652189825");
+
+    const MONTH: DateFormat = DateFormat::new("[month repr:long] [day padding:space]");
+    assert_eq!(Date(date!(2022 - 05 - 01), MONTH).to_string(), "May  1");
+
+    let chrono = chrono::DateTime::parse_from_rfc3339("2022-05-10T13:37:05+02:00").unwrap();
+    const ISO: DateFormat =
+        DateFormat::new("[year]-[month]-[day] [hour]:[minute] [offset_hour]:[offset_minute]");
+    assert_eq!(Date(&chrono, ISO).to_string(), "2022-05-10 13:37 02:00");
+}
+
+#[test]
+fn test_relative() {
+    let now = datetime!(2022-05-10 12:00 UTC);
+    let relative = |value: time::OffsetDateTime| Relative::new(value).now(now).to_string();
+    assert_eq!(relative(datetime!(2022-05-10 11:59:30 UTC)), "just now");
+    assert_eq!(relative(datetime!(2022-05-10 11:59:00 UTC)), "1 minute ago");
+    assert_eq!(relative(datetime!(2022-05-10 09:10:00 UTC)), "3 hours ago");
+    assert_eq!(relative(datetime!(2022-05-12 12:00:00 UTC)), "in 2 days");
+    assert_eq!(relative(datetime!(2022-02-10 12:00:00 UTC)), "3 months ago");
+    assert_eq!(relative(datetime!(2020-05-10 12:00:00 UTC)), "2 years ago");
+}
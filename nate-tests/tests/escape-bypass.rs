@@ -0,0 +1,99 @@
+//! Values that are printed verbatim by the HTML escaper have to be escaped by all other escapers
+
+use std::fmt::{Display, Result, Write};
+use std::net::{IpAddr, Ipv6Addr};
+
+use nate::{
+    CssIdentEscape, CssStringEscape, JsStringEscape, Markup, Nate, Safe, UrlEscape, UrlFormEscape,
+    UrlPathEscape,
+};
+use rust_decimal::Decimal;
+
+#[derive(Nate)]
+#[template(path = "templates/escape-bypass-nested.html")]
+struct Nested<'a>(&'a str);
+
+const NESTED: &str = "<b class=\"x\">x</b>";
+const IP: IpAddr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+macro_rules! test_escaper {
+    ($name:ident, $escape:literal, $wrapper:ident) => {
+        #[test]
+        fn $name() -> Result {
+            #[derive(Nate)]
+            #[template(path = "templates/escape-bypass.txt", escape = $escape)]
+            struct Template<'a> {
+                safe: Markup,
+                nested: Nested<'a>,
+                flag: bool,
+                id: u32,
+                ip: IpAddr,
+            }
+
+            let mut buf = String::new();
+            write!(buf, "{}", Template {
+                safe: Safe::trusted(NESTED.to_owned()),
+                nested: Nested("x"),
+                flag: true,
+                id: 42,
+                ip: IP,
+            })?;
+            let values: [&dyn Display; 5] = [&NESTED, &NESTED, &true, &42, &IP];
+            let expected = values
+                .iter()
+                .map(|value| $wrapper(value).to_string())
+                .collect::<Vec<_>>()
+                .join("|");
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+    };
+}
+
+test_escaper!(test_url, "url", UrlEscape);
+test_escaper!(test_url_path, "url_path", UrlPathEscape);
+test_escaper!(test_url_form, "url_form", UrlFormEscape);
+test_escaper!(test_js, "js", JsStringEscape);
+test_escaper!(test_css, "css", CssStringEscape);
+test_escaper!(test_css_ident, "css_ident", CssIdentEscape);
+
+#[test]
+fn test_js_string_cannot_be_closed() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/js-escape.html", escape = "js")]
+    struct Template {
+        name: Markup,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: Safe::trusted(NESTED.to_owned()),
+    })?;
+    assert_eq!(
+        buf,
+        "<script>\nvar name = \"\\x3Cb class=\\x22x\\x22\\x3Ex\\x3C/b\\x3E\";\n</script>\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn test_css_ident_numbers_and_addresses() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/escape-bypass-css.txt", escape = "css_ident")]
+    struct Template {
+        id: u32,
+        ratio: f32,
+        ip: Ipv6Addr,
+        price: Decimal,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        id: 42,
+        ratio: 1.5,
+        ip: Ipv6Addr::LOCALHOST,
+        price: Decimal::new(995, 2),
+    })?;
+    assert_eq!(buf, ".\\34 2, .\\31 \\.5, .\\:\\:1, .\\39 \\.95 {}\n");
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use std::fmt::{Result, Write};
+
+use nate::Nate;
+
+#[test]
+fn test_escape_override() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/escape-override.html")]
+    struct Template<'a> {
+        query: &'a str,
+        class: &'a str,
+        count: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        query: "Tom & \"Jerry\"",
+        class: "1st",
+        count: 42,
+    })?;
+    assert_eq!(
+        buf,
+        "\
+<a href=\"/search?q=Tom%20%26%20%22Jerry%22\">Tom &#38; &#34;Jerry&#34;</a>
+<script>var q = \"TOM \\x26 \\x22JERRY\\x22\";</script>
+<div class=\"\\31 st\">42</div>
+"
+    );
+    Ok(())
+}
@@ -25,3 +25,90 @@ fn test_css() -> Result {
     );
     Ok(())
 }
+
+/// The chunk size of `XmlEscape`, which tests a whole chunk of bytes at once
+const CHUNK: usize = 32;
+
+const SPECIALS: &[(char, &str)] = &[
+    ('"', "&#34;"),
+    ('&', "&#38;"),
+    ('\'', "&#39;"),
+    ('<', "&#60;"),
+    ('>', "&#62;"),
+];
+
+/// A straight-forward implementation of `XmlEscape`
+fn xml_escape(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match SPECIALS.iter().find(|&&(special, _)| special == c) {
+            Some((_, escaped)) => result.push_str(escaped),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+fn filler(len: usize) -> String {
+    (0..len).map(|i| (b'a' + (i % 26) as u8) as char).collect()
+}
+
+#[test]
+fn test_xml_escape_positions() {
+    for &(c, escaped) in SPECIALS {
+        for len in 0..=3 * CHUNK + 4 {
+            for pos in 0..len {
+                let mut s = filler(len);
+                s.replace_range(pos..pos + 1, &c.to_string());
+                let expected = format!("{}{}{}", &s[..pos], escaped, &s[pos + 1..]);
+                assert_eq!(nate::XmlEscape(&s).to_string(), expected);
+            }
+        }
+    }
+    assert_eq!(
+        nate::XmlEscape("äöü€ = (1+2)*3;").to_string(),
+        "äöü€ = (1+2)*3;"
+    );
+}
+
+#[test]
+fn test_xml_escape_chunk_boundaries() {
+    let boundaries = [CHUNK - 1, CHUNK, 2 * CHUNK - 1, 2 * CHUNK];
+    for len in [2 * CHUNK + 1, 3 * CHUNK, 3 * CHUNK + 7] {
+        for &(a, _) in SPECIALS {
+            for &(b, _) in SPECIALS {
+                for (i, &first) in boundaries.iter().enumerate() {
+                    for &second in &boundaries[i + 1..] {
+                        let mut s = filler(len);
+                        s.replace_range(first..first + 1, &a.to_string());
+                        s.replace_range(second..second + 1, &b.to_string());
+                        assert_eq!(nate::XmlEscape(&s).to_string(), xml_escape(&s), "{:?}", s);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_xml_escape_multibyte() {
+    for c in ['ä', '€', '🦀'] {
+        for boundary in [CHUNK, 2 * CHUNK, 3 * CHUNK] {
+            // the multibyte character starts before the boundary and ends after it
+            for before in 1..c.len_utf8() {
+                for &(special, _) in SPECIALS {
+                    for gap in 0..3 {
+                        let mut s = filler(boundary - before);
+                        s.push(c);
+                        s.push_str(&filler(gap));
+                        s.push(special);
+                        s.push_str(&filler(CHUNK));
+                        s.push(c);
+                        s.push(special);
+                        assert_eq!(nate::XmlEscape(&s).to_string(), xml_escape(&s), "{:?}", s);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Result, Write};
+
+use nate::filters::*;
+use nate::Nate;
+
+#[test]
+fn test_filters_in_template() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/filters-library.html")]
+    struct Template<'a> {
+        name: &'a str,
+        text: &'a str,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: "  kim <the-GREAT>  ",
+        text: "first line\r\n<second> line",
+    })?;
+    assert_eq!(
+        buf,
+        concat!(
+            "  Kim &#60;The-Great&#62;  \n",
+            "  kim &#60;…\n",
+            "[  kim &#60;the-GREAT&#62;   ]\n",
+            "[     KIM &#60;THE-GREAT&#62;]\n",
+            "first line<br>\n",
+            "&#60;second&#62; line\n",
+        ),
+    );
+    Ok(())
+}
+
+#[test]
+fn test_case() {
+    assert_eq!(upper("Straße").to_string(), "STRASSE");
+    assert_eq!(lower("HeLLo").to_string(), "hello");
+    assert_eq!(
+        title("hello wORLD (foo-bar)").to_string(),
+        "Hello World (Foo-Bar)"
+    );
+}
+
+#[test]
+fn test_truncate() {
+    assert_eq!(truncate("hello", 5, "…").to_string(), "hello");
+    assert_eq!(truncate("hello world", 5, "…").to_string(), "hell…");
+    assert_eq!(truncate("hello world", 8, "...").to_string(), "hello...");
+    assert_eq!(truncate(1234567, 4, "").to_string(), "1234");
+    assert_eq!(truncate("hello", 0, "").to_string(), "");
+    assert_eq!(truncate("abcdef", 1, "...").to_string(), ".");
+    assert_eq!(truncate("abcdef", 0, "...").to_string(), "");
+}
+
+#[test]
+fn test_indent() {
+    assert_eq!(indent("a\nb\n\nc", 2).to_string(), "a\n  b\n\n  c");
+}
+
+#[test]
+fn test_wordwrap() {
+    assert_eq!(
+        wordwrap("the quick  brown fox jumps over\nthe lazy dog", 10).to_string(),
+        "the quick\nbrown fox\njumps over\nthe lazy\ndog",
+    );
+    assert_eq!(
+        wordwrap("a incomprehensibilities b", 5).to_string(),
+        "a\nincomprehensibilities\nb",
+    );
+    let long = "x".repeat(200);
+    assert_eq!(
+        wordwrap(format_args!("a {} b", long), 5).to_string(),
+        format!("a\n{}\nb", long),
+    );
+}
+
+#[test]
+fn test_justify() {
+    assert_eq!(center("ab", 7).to_string(), "  ab   ");
+    assert_eq!(ljust("ab", 4).to_string(), "ab  ");
+    assert_eq!(rjust("ab", 4).to_string(), "  ab");
+    assert_eq!(rjust("abcdef", 4).to_string(), "abcdef");
+    assert_eq!(format!("{:*^9}", upper("ab")), "***AB****");
+}
+
+#[test]
+fn test_trim() {
+    assert_eq!(trim("  a b \n").to_string(), "a b");
+    assert_eq!(trim(" \t ").to_string(), "");
+    assert_eq!(trim(format_args!(" {}{} ", " a", " b ")).to_string(), "a b");
+}
+
+#[test]
+fn test_linebreaks() {
+    assert_eq!(linebreaks("a&b\nc").to_string(), "a&#38;b<br>\nc");
+}
+
+#[test]
+fn test_linebreaks_split_crlf() {
+    struct Chunks;
+
+    impl std::fmt::Display for Chunks {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
+            f.write_str("a\r")?;
+            f.write_str("\nb\r")?;
+            f.write_str("")?;
+            f.write_str("c\r")
+        }
+    }
+
+    assert_eq!(linebreaks(Chunks).to_string(), "a<br>\nb\rc\r");
+}
@@ -0,0 +1,75 @@
+use std::fmt::{self, Result, Write};
+
+use nate::Nate;
+
+fn suffix<T: fmt::Display>(value: T, suffix: &str) -> String {
+    format!("{}{}", value, suffix)
+}
+
+#[test]
+fn test_filters() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/filters.html")]
+    struct Template<'a> {
+        name: &'a str,
+        html: &'a str,
+        flags: u8,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: "<World>",
+        html: "<b>bold</b>",
+        flags: 4,
+    })?;
+    assert_eq!(
+        buf,
+        "\
+&#60;WORLD&#62;
+&#60;world&#62;!
+<b>bold</b>
+  &#60;WORLD&#62;?
+5
+6
+"
+    );
+    Ok(())
+}
+
+/// Has the same name as a type in `nate::filters`
+struct Justify;
+
+impl fmt::Display for Justify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("*")
+    }
+}
+
+fn around<T: fmt::Display, U: fmt::Display>(value: T, around: U) -> String {
+    format!("{}{}{}", around, value, around)
+}
+
+/// Has the same name as a filter in `nate::filters`
+fn upper<T: fmt::Display>(value: T) -> String {
+    format!("{}!", value).to_uppercase()
+}
+
+#[test]
+fn test_filter_scope() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/filters-scope.html")]
+    struct Template<'a> {
+        name: &'a str,
+        flags: u8,
+        mask: u8,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: "world",
+        flags: 4,
+        mask: 8,
+    })?;
+    assert_eq!(buf, "*world*\nWORLD!\nWORLD\n5\n12\n");
+    Ok(())
+}
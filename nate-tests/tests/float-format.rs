@@ -0,0 +1,103 @@
+use std::fmt::{Result, Write};
+
+use nate::fmt::FloatFormat;
+use nate::{Nate, Safe};
+
+#[test]
+fn test_float_format_in_template() -> Result {
+    #[derive(Nate)]
+    #[template(
+        path = "templates/float-format.html",
+        float(fixed = 2, nan = "–", infinity = "∞")
+    )]
+    struct Template {
+        total: f64,
+        ratio: f32,
+        missing: f64,
+        limit: f64,
+        count: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        total: 0.1 + 0.2,
+        ratio: 2.0 / 3.0,
+        missing: f64::NAN,
+        limit: f64::NEG_INFINITY,
+        count: 7,
+    })?;
+    assert_eq!(buf, "0.30 0.67 – -∞ 7 0.30000000000000004\n");
+    Ok(())
+}
+
+#[test]
+fn test_float_format_other_values() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/float-format-other.html", float(fixed = 2))]
+    struct Template<'a> {
+        name: &'a str,
+        markup: Safe<&'a str>,
+        price: &'a f64,
+        count: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: "<b>",
+        markup: Safe::trusted("<i>x</i>"),
+        price: &9.5,
+        count: 3,
+    })?;
+    assert_eq!(buf, "&#60;b&#62; <i>x</i> 9.50 3\n");
+    Ok(())
+}
+
+#[test]
+fn test_float_format_default() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/float-format.html")]
+    struct Template {
+        total: f64,
+        ratio: f32,
+        missing: f64,
+        limit: f64,
+        count: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        total: 0.1 + 0.2,
+        ratio: 0.5,
+        missing: f64::NAN,
+        limit: f64::INFINITY,
+        count: 7,
+    })?;
+    assert_eq!(
+        buf,
+        "0.30000000000000004 0.5 NaN inf 7 0.30000000000000004\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_float_format() {
+    let significant = FloatFormat::new().significant(3);
+    assert_eq!(significant.format(1.23456).to_string(), "1.23");
+    assert_eq!(significant.format(0.000123456).to_string(), "0.000123");
+    assert_eq!(significant.format(9.999).to_string(), "10.0");
+    assert_eq!(significant.format(1.5).to_string(), "1.50");
+    assert_eq!(significant.format(0.0).to_string(), "0.00");
+    assert_eq!(significant.format(-123456.0).to_string(), "-123000");
+
+    let fixed = FloatFormat::new().fixed(1);
+    assert_eq!(fixed.format(2.25).to_string(), "2.2");
+    assert_eq!(fixed.format(-0.04).to_string(), "0.0");
+    assert_eq!(fixed.format(1e16).to_string(), "10000000000000000.0");
+    assert_eq!(format!("[{:>6}]", fixed.format(2.5)), "[   2.5]");
+
+    let plain = FloatFormat::new().no_exponent();
+    assert_eq!(FloatFormat::new().format(1e16).to_string(), "1e16");
+    assert_eq!(plain.format(1e16).to_string(), "10000000000000000");
+    assert_eq!(plain.format(-1.5e-7).to_string(), "-0.00000015");
+    assert_eq!(plain.format(0.1 + 0.2).to_string(), "0.30000000000000004");
+}
@@ -0,0 +1,90 @@
+use fluent_bundle::{FluentBundle, FluentResource};
+use nate::i18n::Localized;
+use nate::{Nate, RenderInto};
+use unic_langid::LanguageIdentifier;
+
+#[derive(Nate)]
+#[template(path = "templates/fluent.html")]
+struct Template<'a> {
+    user: &'a str,
+    unread: usize,
+    footer: Footer,
+}
+
+#[derive(Nate)]
+#[template(path = "templates/fluent-footer.html")]
+struct Footer {
+    year: u32,
+}
+
+fn bundle(locale: &str, source: &str) -> FluentBundle<FluentResource> {
+    let locale: LanguageIdentifier = locale.parse().unwrap();
+    let mut bundle = FluentBundle::new(vec![locale]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(source.to_owned()).unwrap())
+        .unwrap();
+    bundle
+}
+
+#[test]
+fn test_fluent() {
+    let en = bundle("en", include_str!("../locales/en/main.ftl"));
+    let de = bundle("de", include_str!("../locales/de/main.ftl"));
+
+    let template = Template {
+        user: "<World>",
+        unread: 1,
+        footer: Footer { year: 2022 },
+    };
+    assert_eq!(
+        Localized::new(&template, &en).to_string(),
+        "<p>Welcome, <b>&#60;World&#62;</b>!</p>
+<p>You have one unread message.</p>
+<footer>© 2022 NaTE</footer>
+
+",
+    );
+
+    let template = Template {
+        user: "Ferris",
+        unread: 3,
+        footer: Footer { year: 2023 },
+    };
+    let mut output = String::new();
+    Localized::new(&template, &de)
+        .render_string(&mut output)
+        .unwrap();
+    assert_eq!(
+        output,
+        "<p>Willkommen, <b>Ferris</b>!</p>
+<p>Sie haben 3 ungelesene Nachrichten.</p>
+<footer>© 2023 NaTE</footer>
+
+",
+    );
+}
+
+#[test]
+fn test_fluent_without_locale() {
+    let template = Footer { year: 2022 };
+    assert_eq!(template.to_string(), "<footer>footer</footer>\n");
+}
+
+#[test]
+fn test_fluent_strings_that_look_like_numbers() {
+    #[derive(Nate)]
+    #[template(path = "templates/fluent-strings.html")]
+    struct Template<'a> {
+        phone: &'a str,
+    }
+
+    let en = bundle("en", include_str!("../locales/en/main.ftl"));
+    for phone in ["01234", "+49", "1e3", "Infinity", "-0"] {
+        let template = Template { phone };
+        assert_eq!(
+            Localized::new(&template, &en).to_string(),
+            format!("Call {}.\n", phone),
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt::{Result, Write};
+
+use nate::{Nate, Safe};
+
+#[test]
+fn test_format_spec() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/format-spec.html")]
+    struct Template<'a> {
+        price: f64,
+        id: u32,
+        name: &'a str,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        price: 0.1 + 0.2,
+        id: 4711,
+        name: "<a>",
+    })?;
+    assert_eq!(
+        buf,
+        "\
+0.30
+    4711|
+0x1267
+---&#60;a&#62;---
+<a>   |
+3.142
+9424
++4711|+4711|  4711|
+"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_format_spec_verbatim() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/nested-child.html")]
+    struct Child<'a> {
+        name: &'a str,
+    }
+
+    #[derive(Nate)]
+    #[template(path = "templates/format-spec-verbatim.html")]
+    struct Template<'a> {
+        markup: Safe<&'a str>,
+        child: Child<'a>,
+        text: &'a str,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        markup: Safe::trusted("<i>trusted</i>"),
+        child: Child { name: "<a>" },
+        text: "<a>",
+    })?;
+    assert_eq!(
+        buf,
+        concat!(
+            "      <i>trusted</i>|\n",
+            "<b>&#60;a&#62;</b>\n|\n",
+            "     &#60;a&#62;|\n",
+        ),
+    );
+    Ok(())
+}
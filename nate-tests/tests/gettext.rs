@@ -0,0 +1,145 @@
+use nate::i18n::{Catalog, CatalogError, Localized};
+use nate::Nate;
+
+#[derive(Nate)]
+#[template(path = "templates/gettext.html", pot = "po/gettext.pot")]
+struct Template<'a> {
+    user: &'a str,
+    unread: usize,
+    footer: Footer,
+}
+
+#[derive(Nate)]
+#[template(path = "templates/gettext-footer.html", pot = "po/gettext.pot")]
+struct Footer;
+
+#[test]
+fn test_gettext() {
+    let de = Catalog::parse("de-DE", include_str!("../po/de.po")).unwrap();
+
+    let template = Template {
+        user: "<World>",
+        unread: 1,
+        footer: Footer,
+    };
+    assert_eq!(
+        Localized::new(&template, &de).to_string(),
+        "<p>Willkommen, <b>&#60;World&#62;</b>!</p>
+<p>Sie haben eine neue Nachricht.</p>
+<footer>Powered by NaTE</footer>
+
+",
+    );
+
+    let template = Template {
+        user: "Ferris",
+        unread: 3,
+        footer: Footer,
+    };
+    assert_eq!(
+        Localized::new(&template, &de).to_string(),
+        "<p>Willkommen, <b>Ferris</b>!</p>
+<p>Sie haben 3 neue Nachrichten.</p>
+<footer>Powered by NaTE</footer>
+
+",
+    );
+}
+
+#[test]
+fn test_gettext_untranslated() {
+    let template = Template {
+        user: "Ferris",
+        unread: 1,
+        footer: Footer,
+    };
+    assert_eq!(
+        template.to_string(),
+        "<p>Welcome, <b>Ferris</b>!</p>
+<p>You have one new message.</p>
+<footer>Powered by NaTE</footer>
+
+",
+    );
+
+    let template = Template {
+        user: "Ferris",
+        unread: 0,
+        footer: Footer,
+    };
+    assert_eq!(
+        template.to_string(),
+        "<p>Welcome, <b>Ferris</b>!</p>
+<p>You have 0 new messages.</p>
+<footer>Powered by NaTE</footer>
+
+",
+    );
+}
+
+#[test]
+fn test_plural_rules() {
+    let pl = Catalog::parse("pl", include_str!("../po/pl.po")).unwrap();
+    let id = "You have one new message.";
+    assert_eq!(pl.get(id, Some("1")), Some("Masz jedną nową wiadomość."));
+    assert_eq!(pl.get(id, Some("3")), Some("Masz {count} nowe wiadomości."));
+    assert_eq!(
+        pl.get(id, Some("5")),
+        Some("Masz {count} nowych wiadomości.")
+    );
+    assert_eq!(
+        pl.get(id, Some("22")),
+        Some("Masz {count} nowe wiadomości.")
+    );
+    assert_eq!(
+        pl.get(id, Some("1.5")),
+        Some("Masz {count} nowych wiadomości.")
+    );
+    assert_eq!(pl.get("unknown", None), None);
+}
+
+#[test]
+fn test_catalog_errors() {
+    assert_eq!(
+        Catalog::parse("xx-invalid-", "").unwrap_err(),
+        CatalogError::Locale
+    );
+    assert_eq!(
+        Catalog::parse("en", "msgid \"a\"\nmsgstr \"b").unwrap_err(),
+        CatalogError::Syntax(2),
+    );
+    assert_eq!(
+        Catalog::parse("en", "msgid \"a\"\nmsgstr[1] \"b\"").unwrap_err(),
+        CatalogError::Syntax(2),
+    );
+}
+
+#[test]
+fn test_pot() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/po/gettext.pot");
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Content-Transfer-Encoding: 8bit\n"
+
+#: templates/gettext-footer.html:1
+#, python-brace-format
+msgid "Powered by {name}"
+msgstr ""
+
+#: templates/gettext.html:1
+#, python-brace-format
+msgid "Welcome, <b>{user}</b>!"
+msgstr ""
+
+#: templates/gettext.html:2
+#, python-brace-format
+msgid "You have one new message."
+msgid_plural "You have {count} new messages."
+msgstr[0] ""
+msgstr[1] ""
+"#,
+    );
+}
@@ -0,0 +1,56 @@
+use std::fmt::{Result, Write};
+
+use nate::{CssIdentEscape, CssStringEscape, JsStringEscape, Nate};
+
+#[test]
+fn test_js_escape_template() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/js-escape.html", escape = "js")]
+    struct Template<'a> {
+        name: &'a str,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: "</script><script>alert('\"hi\"')",
+    })?;
+    assert_eq!(
+        buf,
+        "<script>\n\
+         var name = \"\\x3C/script\\x3E\\x3Cscript\\x3Ealert(\\x27\\x22hi\\x22\\x27)\";\n\
+         </script>\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn test_js_string_escape() {
+    assert_eq!(
+        JsStringEscape("a\\b\n\r\t`\u{2028}\u{2029}ä&").to_string(),
+        "a\\\\b\\n\\r\\t\\x60\\u2028\\u2029ä\\x26",
+    );
+    assert_eq!(format!("{:?}", JsStringEscape("a")), "\\x22a\\x22");
+}
+
+#[test]
+fn test_css_string_escape() {
+    assert_eq!(
+        CssStringEscape("a\"b'c\\d</style>\n").to_string(),
+        "a\\22 b\\27 c\\5C d\\3C /style\\3E \\A ",
+    );
+}
+
+#[test]
+fn test_css_ident_escape() {
+    assert_eq!(CssIdentEscape("foo-bar_1").to_string(), "foo-bar_1");
+    assert_eq!(CssIdentEscape("1a").to_string(), "\\31 a");
+    assert_eq!(CssIdentEscape("-1a").to_string(), "-\\31 a");
+    assert_eq!(CssIdentEscape("-").to_string(), "\\-");
+    assert_eq!(CssIdentEscape("--x").to_string(), "--x");
+    assert_eq!(CssIdentEscape("a.b#c d").to_string(), "a\\.b\\#c\\ d");
+    assert_eq!(CssIdentEscape("<ä>").to_string(), "\\3C ä\\3E ");
+    assert_eq!(
+        CssIdentEscape(format_args!("{}{}", "-", 2)).to_string(),
+        "-\\32 "
+    );
+}
@@ -0,0 +1,41 @@
+use std::fmt::{Result, Write};
+
+use nate::{Json, Nate};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Config<'a> {
+    title: &'a str,
+    limit: u32,
+    tags: &'a [&'a str],
+}
+
+#[test]
+fn test_json() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/json.html")]
+    struct Template<'a> {
+        config: Config<'a>,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        config: Config {
+            title: "</script><b>'Tom' & \"Jerry\"</b>",
+            limit: 10,
+            tags: &["a\u{2028}b"],
+        },
+    })?;
+    assert_eq!(
+        buf,
+        r#"<script>var config = {"title":"\u003c/script\u003e\u003cb\u003e\u0027Tom\u0027 \u0026 \"Jerry\"\u003c/b\u003e","limit":10,"tags":["a\u2028b"]};</script>
+<div data-config="{&#34;title&#34;:&#34;\u003c/script\u003e\u003cb\u003e\u0027Tom\u0027 \u0026 \&#34;Jerry\&#34;\u003c/b\u003e&#34;,&#34;limit&#34;:10,&#34;tags&#34;:[&#34;a\u2028b&#34;]}"></div>
+<script src="/app.js" data-config="[&#34;a\u2028b&#34;]"></script>
+<script type="module">
+  let limit = 10, title = "&#60;/script&#62;&#60;b&#62;&#39;Tom&#39; &#38; &#34;Jerry&#34;&#60;/b&#62;";
+</script>
+[&#34;a\u2028b&#34;]
+"#
+    );
+    Ok(())
+}
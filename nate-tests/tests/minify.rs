@@ -0,0 +1,76 @@
+use std::fmt::{Result, Write};
+
+use nate::Nate;
+
+#[derive(Nate)]
+#[template(path = "templates/minify.html", minify = "html")]
+struct Page<'a> {
+    title: &'a str,
+    name: &'a str,
+    url: &'a str,
+    items: &'a [&'a str],
+}
+
+#[test]
+fn test_minify_html() -> Result {
+    let template = Page {
+        title: "Minify",
+        name: "<World>  ",
+        url: "/a b",
+        items: &["one", "two"],
+    };
+    let mut buf = String::new();
+    write!(buf, "{}", template)?;
+    assert_eq!(
+        buf,
+        r#"<!DOCTYPE html><html lang=en><head><title>Minify</title><style>
+      body { margin: 0; }
+    </style></head><body class="main page"><h1 id=title data-x=1>Hello, <b>&#60;World&#62;  </b> !</h1><a href="/a b" title="">Link</a><pre>
+  keep   this
+    </pre><textarea name=text>  &#60;World&#62;    </textarea><ul><li>one</li><li>two</li></ul><script>
+      if (a < b) { alert("  hi  "); }
+    </script></body></html>"#,
+    );
+    Ok(())
+}
+
+#[derive(Nate)]
+#[template(path = "templates/minify.js", minify = "js", escape = "js")]
+struct Script<'a> {
+    user: &'a str,
+    count: u32,
+}
+
+#[test]
+fn test_minify_js() -> Result {
+    let template = Script {
+        user: "\"Alice\"",
+        count: 2,
+    };
+    let mut buf = String::new();
+    write!(buf, "{}", template)?;
+    assert_eq!(
+        buf,
+        r#"const user="\x22Alice\x22";let count=2;function greet(name){if(count>0&&/^\w+ \//.test(name)){return`Hello, ${ name }!   `+-count;}
+return name.trim();}
+/*! keep the license */
+let a=count
+++count
+alert(greet(user)/2);count++/2;const s="a  /  b";"#,
+    );
+    Ok(())
+}
+
+#[test]
+fn test_minify_js_comment_with_value() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/minify-comment.js", minify = "js", escape = "js")]
+    struct Script {
+        a: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Script { a: 1 })?;
+    assert_eq!(buf, "// by 1 here\nfoo();/* 1 */bar();// 1 =\nbaz();");
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::fmt::{Result, Write};
+
+use nate::{Nate, RenderInto};
+
+#[derive(Nate)]
+#[template(path = "templates/nested-child.html")]
+struct Child<'a> {
+    name: &'a str,
+}
+
+#[derive(Nate)]
+#[template(path = "templates/nested-parent.html")]
+struct Parent<'a> {
+    title: &'a str,
+    child: Child<'a>,
+    by_ref: &'a Child<'a>,
+    children: &'a [Child<'a>],
+}
+
+#[test]
+fn test_nested() -> Result {
+    let template = Parent {
+        title: "<Title>",
+        child: Child { name: "<first>" },
+        by_ref: &Child { name: "<second>" },
+        children: &[Child { name: "a&b" }, Child { name: "c" }],
+    };
+    let expected = "\
+<p>&#60;Title&#62;</p>
+<b>&#60;first&#62;</b>
+<b>&#60;second&#62;</b>
+<b>&#60;first&#62;</b>
+<i><b>a&#38;b</b>
+</i><i><b>c</b>
+</i>
+";
+
+    let mut buf = String::new();
+    write!(buf, "{}", template)?;
+    assert_eq!(buf, expected);
+
+    let mut buf = String::new();
+    template.render_string(&mut buf)?;
+    assert_eq!(buf, expected);
+
+    let mut buf = Vec::new();
+    template.render_io(&mut buf)?;
+    assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    Ok(())
+}
+
+#[derive(Nate)]
+#[template(path = "templates/nested-tree.html")]
+#[allow(clippy::vec_box)] // test `impl RenderInto for Box<T>`
+struct Tree {
+    name: &'static str,
+    children: Vec<Box<Tree>>,
+}
+
+#[test]
+fn test_nested_recursive() -> Result {
+    let template = Tree {
+        name: "<root>",
+        children: vec![
+            Box::new(Tree {
+                name: "a",
+                children: vec![Box::new(Tree {
+                    name: "b",
+                    children: vec![],
+                })],
+            }),
+            Box::new(Tree {
+                name: "c",
+                children: vec![],
+            }),
+        ],
+    };
+
+    let mut buf = String::new();
+    template.render_string(&mut buf)?;
+    assert_eq!(
+        buf,
+        "<ul><li>&#60;root&#62;</li><ul><li>a</li><ul><li>b</li></ul>\n</ul>\n<ul><li>c</li></ul>\n</ul>\n",
+    );
+    Ok(())
+}
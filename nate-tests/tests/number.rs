@@ -0,0 +1,142 @@
+use std::fmt::{Result, Write};
+
+use nate::fmt::{Locale, Number, Rounding, SignDisplay};
+use nate::Nate;
+
+#[test]
+fn test_number_in_template() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/number.html")]
+    struct Template {
+        total: f64,
+        share: f32,
+        views: u64,
+        delta: i32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        total: 1234567.891,
+        share: 0.1234,
+        views: 1_260_000,
+        delta: 7,
+    })?;
+    assert_eq!(
+        buf,
+        "Total: 1,234,567.89 / 1.234.567,89
+Share: 12,3\u{202f}%
+Views: 1.3M
+Delta: +7
+[   1,260,000]
+"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_grouping() {
+    assert_eq!(Number::new(0).to_string(), "0");
+    assert_eq!(Number::new(999).to_string(), "999");
+    assert_eq!(Number::new(1000).to_string(), "1,000");
+    assert_eq!(Number::new(-1234567).to_string(), "-1,234,567");
+    assert_eq!(Number::new(1234567).grouping(false).to_string(), "1234567");
+    assert_eq!(Number::new(1234).locale(Locale::ES).to_string(), "1234");
+    assert_eq!(Number::new(12345).locale(Locale::ES).to_string(), "12.345");
+    assert_eq!(
+        Number::new(1234567.5).locale(Locale::DE_CH).to_string(),
+        "1\u{2019}234\u{2019}567.5"
+    );
+    assert_eq!(
+        Number::new(u128::MAX).to_string(),
+        "340,282,366,920,938,463,463,374,607,431,768,211,455"
+    );
+}
+
+#[test]
+fn test_precision() {
+    assert_eq!(Number::new(0.1 + 0.2).to_string(), "0.30000000000000004");
+    assert_eq!(Number::new(0.1 + 0.2).precision(2).to_string(), "0.30");
+    assert_eq!(Number::new(5).precision(2).to_string(), "5.00");
+    assert_eq!(Number::new(1e20).to_string(), "100,000,000,000,000,000,000");
+    assert_eq!(Number::new(1.5e-7).to_string(), "0.00000015");
+    assert_eq!(Number::new(1.5e-7).precision(3).to_string(), "0.000");
+    assert_eq!(Number::new(999.996).precision(2).to_string(), "1,000.00");
+    assert_eq!(Number::new(-0.001).precision(2).to_string(), "0.00");
+    assert_eq!(Number::new(f64::NAN).to_string(), "NaN");
+    assert_eq!(Number::new(f64::NEG_INFINITY).to_string(), "-∞");
+}
+
+#[test]
+fn test_rounding() {
+    let round = |value: f64, rounding| {
+        Number::new(value)
+            .precision(0)
+            .rounding(rounding)
+            .to_string()
+    };
+    assert_eq!(round(2.5, Rounding::HalfEven), "2");
+    assert_eq!(round(3.5, Rounding::HalfEven), "4");
+    assert_eq!(round(2.5, Rounding::HalfUp), "3");
+    assert_eq!(round(-2.5, Rounding::HalfUp), "-3");
+    assert_eq!(round(2.1, Rounding::Up), "3");
+    assert_eq!(round(-2.9, Rounding::Down), "-2");
+    assert_eq!(round(-2.9, Rounding::Ceiling), "-2");
+    assert_eq!(round(-2.1, Rounding::Floor), "-3");
+    assert_eq!(round(0.4, Rounding::Up), "1");
+    assert_eq!(
+        Number::new(0.0004)
+            .precision(2)
+            .rounding(Rounding::Ceiling)
+            .to_string(),
+        "0.01"
+    );
+    assert_eq!(
+        Number::new(1.005)
+            .precision(2)
+            .rounding(Rounding::HalfUp)
+            .to_string(),
+        "1.01"
+    );
+}
+
+#[test]
+fn test_sign_percent_compact() {
+    assert_eq!(Number::new(0).sign(SignDisplay::Always).to_string(), "+0");
+    assert_eq!(
+        Number::new(0).sign(SignDisplay::ExceptZero).to_string(),
+        "0"
+    );
+    assert_eq!(Number::new(-3).sign(SignDisplay::Never).to_string(), "3");
+    assert_eq!(Number::new(0.5).percent().to_string(), "50%");
+    assert_eq!(Number::new(0).percent().to_string(), "0%");
+    assert_eq!(Number::new(0.0).percent().to_string(), "0%");
+    assert_eq!(Number::new(0.0).percent().precision(1).to_string(), "0.0%");
+    assert_eq!(Number::new(0.0004).percent().precision(1).to_string(), "0.0%");
+    assert_eq!(
+        Number::new(0.256).percent().locale(Locale::DE).to_string(),
+        "25,6\u{a0}%"
+    );
+    assert_eq!(Number::new(0).compact().to_string(), "0");
+    assert_eq!(Number::new(999).compact().to_string(), "999");
+    assert_eq!(Number::new(1234).compact().to_string(), "1.2k");
+    assert_eq!(Number::new(-15_000).compact().to_string(), "-15k");
+    assert_eq!(Number::new(999_960).compact().to_string(), "1M");
+    assert_eq!(
+        Number::new(2.5e9).compact().locale(Locale::DE).to_string(),
+        "2,5\u{a0}Mrd."
+    );
+    assert_eq!(Number::new(4.2e18).compact().to_string(), "4,200,000T");
+    assert_eq!(
+        Number::new(1234).compact().precision(2).to_string(),
+        "1.23k"
+    );
+}
+
+#[test]
+fn test_locale_from_tag() {
+    assert_eq!(Locale::from_tag("de"), Some(Locale::DE));
+    assert_eq!(Locale::from_tag("de-AT"), Some(Locale::DE));
+    assert_eq!(Locale::from_tag("de_ch"), Some(Locale::DE_CH));
+    assert_eq!(Locale::from_tag("EN-us"), Some(Locale::EN));
+    assert_eq!(Locale::from_tag("xx"), None);
+}
@@ -0,0 +1,35 @@
+use std::fmt::{Result, Write};
+
+use nate::{Markup, Nate, Safe};
+
+fn bold(text: &str) -> Markup {
+    Safe::trusted(format!("<b>{}</b>", Safe::escape(text)))
+}
+
+#[test]
+fn test_safe() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/safe.html")]
+    struct Template<'a> {
+        name: &'a str,
+        markup: Safe<&'a str>,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        name: "<World>",
+        markup: Safe::trusted("<i>trusted</i>"),
+    })?;
+    assert_eq!(
+        buf,
+        "<p><b>&#60;World&#62;</b>, &#60;World&#62;</p>\n<p><i>trusted</i></p>\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_safe_escape() {
+    let escaped = Safe::escape("a & b");
+    assert_eq!(escaped.to_string(), "a &#38; b");
+    assert_eq!(escaped.to_markup(), Safe::trusted("a &#38; b".to_owned()));
+}
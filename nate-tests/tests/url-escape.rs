@@ -0,0 +1,61 @@
+use std::fmt::{Result, Write};
+
+use nate::{Nate, UrlEscape, UrlFormEscape, UrlPathEscape};
+
+#[test]
+fn test_url_escape_template() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/url-escape.html", escape = "url")]
+    struct Template<'a> {
+        query: &'a str,
+        page: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        query: "<b>Tom & Jerry</b>",
+        page: 2,
+    })?;
+    assert_eq!(
+        buf,
+        "<a href=\"/search?q=%3Cb%3ETom%20%26%20Jerry%3C/b%3E&amp;page=2\">\
+         &#60;b&#62;Tom &#38; Jerry&#60;/b&#62;</a>\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn test_url_form_escape_template() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/url-escape.html", escape = "url_form")]
+    struct Template<'a> {
+        query: &'a str,
+        page: u32,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        query: "Tom & Jerry",
+        page: 3,
+    })?;
+    assert_eq!(
+        buf,
+        "<a href=\"/search?q=Tom+%26+Jerry&amp;page=3\">Tom &#38; Jerry</a>\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn test_url_escape_variants() {
+    let s = "a/b?c=d&e+f g'ä";
+    assert_eq!(UrlEscape(s).to_string(), "a/b?c%3Dd%26e%2Bf%20g%27%C3%A4");
+    assert_eq!(
+        UrlPathEscape(s).to_string(),
+        "a%2Fb%3Fc=d%26e+f%20g%27%C3%A4"
+    );
+    assert_eq!(
+        UrlFormEscape(s).to_string(),
+        "a%2Fb%3Fc%3Dd%26e%2Bf+g%27%C3%A4"
+    );
+    assert_eq!(format!("{:?}", UrlEscape("a b")), "%22a%20b%22");
+}
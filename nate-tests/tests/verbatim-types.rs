@@ -0,0 +1,38 @@
+use std::fmt::{Result, Write};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use nate::Nate;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[test]
+fn test_verbatim_types() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/verbatim-types.html")]
+    struct Template {
+        id: Uuid,
+        ip: IpAddr,
+        addr: SocketAddrV4,
+        addr6: SocketAddrV6,
+        socket: SocketAddr,
+        price: Decimal,
+        admin: bool,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        id: Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+        ip: IpAddr::V6(Ipv6Addr::LOCALHOST),
+        addr: "127.0.0.1:8080".parse().unwrap(),
+        addr6: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 0, 2),
+        socket: "[::1]:80".parse().unwrap(),
+        price: Decimal::new(-1999, 2),
+        admin: false,
+    })?;
+    assert_eq!(
+        buf,
+        "<a href=\"/user/67e55044-10b1-426f-9247-bb680e5fe0c8\" data-ip=\"::1\">127.0.0.1:8080</a> \
+         [::1%2]:443 [::1]:80 -19.99 false\n",
+    );
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::fmt::{Result, Write};
+
+use nate::Nate;
+
+const EXPECTED: &str = "\
+items:
+  - a
+  - b
+    selected: true
+  - c
+end: true
+";
+
+#[test]
+fn test_trim_line() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/whitespace-control.yaml")]
+    struct Template<'a> {
+        items: &'a [&'a str],
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        items: &["a", "b", "c"],
+    })?;
+    assert_eq!(buf, EXPECTED);
+    Ok(())
+}
+
+#[test]
+fn test_strip_blocks() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/whitespace-control-blocks.yaml", strip = "blocks")]
+    struct Template<'a> {
+        items: &'a [&'a str],
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template {
+        items: &["a", "b", "c"],
+    })?;
+    assert_eq!(buf, EXPECTED.replace("end:", "\nend:"));
+    Ok(())
+}
+
+#[test]
+fn test_strip_blocks_inline() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/whitespace-control-inline.html", strip = "blocks")]
+    struct Template {
+        x: bool,
+    }
+
+    let mut buf = String::new();
+    write!(buf, "{}", Template { x: true })?;
+    assert_eq!(buf, "Hello world!\n x line\nend\n");
+    Ok(())
+}
+
+#[test]
+fn test_strip_modes() -> Result {
+    #[derive(Nate)]
+    #[template(path = "templates/whitespace-strip.html", strip = "tail")]
+    struct Tail<'a> {
+        items: &'a [&'a str],
+    }
+
+    #[derive(Nate)]
+    #[template(path = "templates/whitespace-strip.html", strip = "trim")]
+    struct Trim<'a> {
+        items: &'a [&'a str],
+    }
+
+    #[derive(Nate)]
+    #[template(path = "templates/whitespace-strip.html", strip = "eager")]
+    struct Eager<'a> {
+        items: &'a [&'a str],
+    }
+
+    let items = &["a", "b"];
+    let mut buf = String::new();
+    write!(buf, "{}", Tail { items })?;
+    assert_eq!(
+        buf,
+        concat!(
+            "<ul>\n    \n        <li>  a  </li>\n    \n        <li>  b  </li>\n    \n</ul>\n",
+            "<p>  a  b   c  </p>",
+        ),
+    );
+    buf.clear();
+    write!(buf, "{}", Trim { items })?;
+    assert_eq!(
+        buf,
+        "<ul>\n\n<li>  a  </li>\n\n<li>  b  </li>\n\n</ul>\n<p>  a  b   c  </p>",
+    );
+    buf.clear();
+    write!(buf, "{}", Eager { items })?;
+    assert_eq!(
+        buf,
+        "<ul>\n\n<li> a </li>\n\n<li> b </li>\n\n</ul>\n<p> a  b c </p>",
+    );
+    Ok(())
+}
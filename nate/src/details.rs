@@ -10,9 +10,15 @@ use core::fmt;
 use core::fmt::Write as _;
 use core::marker::PhantomData;
 
-pub use crate::escape::{EscapeKind, XmlEscape};
-pub use crate::fast_float::FloatKind;
+pub use crate::escape::{
+    CssIdentEscape, CssIdentEscapeKind, CssStringEscape, CssStringEscapeKind, EscapeKind,
+    JsStringEscape, JsStringEscapeKind, UrlEscape, UrlEscapeKind, UrlFormEscape, UrlFormEscapeKind,
+    UrlPathEscape, UrlPathEscapeKind, XmlEscape,
+};
+pub use crate::fast_float::{FloatKind, FloatValue, FloatValueKind, OtherValueKind};
 pub use crate::fast_integer::IntKind;
+#[cfg(any(feature = "fluent", feature = "gettext"))]
+use crate::i18n::Message;
 pub use crate::raw::RawKind;
 
 /// [Zero sized](https://doc.rust-lang.org/1.56.0/nomicon/exotic-sizes.html#zero-sized-types-zsts)
@@ -106,6 +112,38 @@ impl<E> EscapeWrapper<E> {
 pub trait WriteAny {
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> fmt::Result;
     fn write_str(&mut self, s: &str) -> fmt::Result;
+
+    /// Write a translated message, which is untranslated without a [`Localizer`](crate::i18n::Localizer)
+    #[cfg(any(feature = "fluent", feature = "gettext"))]
+    #[inline]
+    fn write_message(&mut self, message: &Message<'_>) -> fmt::Result {
+        message.write_untranslated(&mut crate::i18n::AsFmtWrite(self))
+    }
+}
+
+impl<W: WriteAny + ?Sized> WriteAny for &mut W {
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> fmt::Result {
+        W::write_fmt(self, fmt)
+    }
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        W::write_str(self, s)
+    }
+
+    #[cfg(any(feature = "fluent", feature = "gettext"))]
+    #[inline]
+    fn write_message(&mut self, message: &Message<'_>) -> fmt::Result {
+        W::write_message(self, message)
+    }
+}
+
+/// Write the translated message of a `{< t "message-id" … >}` or `{< _ "Message" … >}` block
+#[cfg(any(feature = "fluent", feature = "gettext"))]
+#[inline]
+pub fn write_message(mut output: impl WriteAny, message: &Message<'_>) -> fmt::Result {
+    output.write_message(message)
 }
 
 #[cfg(feature = "std")]
@@ -192,3 +230,300 @@ pub trait RenderInto {
         self.render_into(WriteString(output))
     }
 }
+
+impl<T: RenderInto + ?Sized> RenderInto for &T {
+    #[inline]
+    fn render_into(&self, output: impl WriteAny) -> fmt::Result {
+        T::render_into(self, output)
+    }
+}
+
+impl<T: RenderInto + ?Sized> RenderInto for &mut T {
+    #[inline]
+    fn render_into(&self, output: impl WriteAny) -> fmt::Result {
+        T::render_into(self, output)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+const _: () = {
+    impl<T: RenderInto + ?Sized> RenderInto for alloc::boxed::Box<T> {
+        #[inline]
+        fn render_into(&self, output: impl WriteAny) -> fmt::Result {
+            T::render_into(self, output)
+        }
+    }
+
+    impl<T: RenderInto + ?Sized> RenderInto for alloc::rc::Rc<T> {
+        #[inline]
+        fn render_into(&self, output: impl WriteAny) -> fmt::Result {
+            T::render_into(self, output)
+        }
+    }
+
+    impl<T: RenderInto + ?Sized> RenderInto for alloc::sync::Arc<T> {
+        #[inline]
+        fn render_into(&self, output: impl WriteAny) -> fmt::Result {
+            T::render_into(self, output)
+        }
+    }
+};
+
+/// Optimized trait methods to render a NaTE template with a render context
+///
+/// Every NaTE template that was declared with `#[template(context = "…")]` implements this trait
+/// instead of [`RenderInto`]. The template can access the context as the variable `ctx`, and
+/// nested templates are rendered with the same context.
+pub trait RenderWith {
+    /// The type of the render context, e.g. `RequestContext`
+    type Context: ?Sized;
+
+    #[doc(hidden)]
+    fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result;
+
+    /// Render the output into an [`fmt::Write`](std::fmt::Write) object with the render context
+    #[inline]
+    fn render_with(&self, ctx: &Self::Context, output: impl fmt::Write) -> fmt::Result {
+        self.render_with_into(ctx, WriteFmt(output))
+    }
+
+    /// Render the output into an [`io::Write`](std::io::Write) object with the render context
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    fn render_with_io(&self, ctx: &Self::Context, output: impl std::io::Write) -> fmt::Result {
+        self.render_with_into(ctx, WriteIo(output))
+    }
+
+    /// Render the output into a [`String`] with the render context
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    fn render_with_string(
+        &self,
+        ctx: &Self::Context,
+        output: &mut alloc::string::String,
+    ) -> fmt::Result {
+        self.render_with_into(ctx, WriteString(output))
+    }
+}
+
+impl<T: RenderWith + ?Sized> RenderWith for &T {
+    type Context = T::Context;
+
+    #[inline]
+    fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result {
+        T::render_with_into(self, ctx, output)
+    }
+}
+
+impl<T: RenderWith + ?Sized> RenderWith for &mut T {
+    type Context = T::Context;
+
+    #[inline]
+    fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result {
+        T::render_with_into(self, ctx, output)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+const _: () = {
+    impl<T: RenderWith + ?Sized> RenderWith for alloc::boxed::Box<T> {
+        type Context = T::Context;
+
+        #[inline]
+        fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result {
+            T::render_with_into(self, ctx, output)
+        }
+    }
+
+    impl<T: RenderWith + ?Sized> RenderWith for alloc::rc::Rc<T> {
+        type Context = T::Context;
+
+        #[inline]
+        fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result {
+            T::render_with_into(self, ctx, output)
+        }
+    }
+
+    impl<T: RenderWith + ?Sized> RenderWith for alloc::sync::Arc<T> {
+        type Context = T::Context;
+
+        #[inline]
+        fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result {
+            T::render_with_into(self, ctx, output)
+        }
+    }
+};
+
+// The generated code calls `(&&&&&EscapeWrapper::new(value)).wrap(value)`, so this impl takes
+// precedence over `RawKind`, which every template implements, too.
+impl<T: RenderInto> RenderKind for &&&&EscapeWrapper<T> {}
+
+impl<T: RenderInto> RenderRawKind for &&&&EscapeWrapper<T> {}
+
+impl<T: RenderWith> RenderWithKind for &&&&EscapeWrapper<T> {}
+
+impl<T: fmt::Display> DisplayKind for EscapeWrapper<T> {}
+
+impl<T: ScriptMarker> ScriptKind for &&&&&EscapeWrapper<T> {}
+
+/// Types implementing this marker are printed verbatim inside of a `<script>` element
+///
+/// Outside of `<script>` elements they are escaped like any other value.
+#[doc(hidden)]
+pub trait ScriptMarker: fmt::Display {}
+
+impl<T: ScriptMarker> ScriptMarker for &T {}
+
+/// Selects values that implement [`ScriptMarker`] in `{{ value blocks }}` inside of a `<script>`
+/// element of an HTML template
+///
+/// The generated code only imports it for these value blocks.
+#[doc(hidden)]
+pub trait ScriptKind {
+    #[inline]
+    fn wrap<'a, T: ScriptMarker>(&self, value: &'a T) -> &'a T {
+        value
+    }
+}
+
+/// Selects [`RenderWrapper`] for values in `{{ value blocks }}` that implement [`RenderInto`]
+///
+/// Only used with the HTML escaper. With other escapers nested templates are escaped, too.
+#[doc(hidden)]
+pub trait RenderKind {
+    #[inline]
+    fn wrap<'a, T: RenderInto>(&self, value: &'a T) -> RenderWrapper<'a, T> {
+        RenderWrapper(value)
+    }
+
+    /// Print a nested template verbatim that was formatted with a format specifier, e.g.
+    /// `{{ template :>20 }}`
+    #[inline]
+    fn wrap_fmt<'a>(&self, formatted: fmt::Arguments<'a>) -> fmt::Arguments<'a> {
+        formatted
+    }
+}
+
+/// Selects [`RenderWrapper`] for values in `{{{ raw blocks }}}` that implement [`RenderInto`]
+#[doc(hidden)]
+pub trait RenderRawKind {
+    #[inline]
+    fn wrap_raw<'a, T: RenderInto>(&self, value: &'a T) -> RenderWrapper<'a, T> {
+        RenderWrapper(value)
+    }
+}
+
+/// Selects [`RenderWithWrapper`] for values in `{{ value }}` and `{{{ raw }}}` blocks that
+/// implement [`RenderWith`]
+///
+/// Only imported by templates with a render context, which pass their context on.
+#[doc(hidden)]
+pub trait RenderWithKind {
+    #[inline]
+    fn wrap<'a, T: RenderWith>(&self, value: &'a T) -> RenderWithWrapper<'a, T> {
+        RenderWithWrapper(value)
+    }
+
+    #[inline]
+    fn wrap_raw<'a, T: RenderWith>(&self, value: &'a T) -> RenderWithWrapper<'a, T> {
+        RenderWithWrapper(value)
+    }
+}
+
+/// Prints values verbatim if no other kind applies
+///
+/// Values in `{{{ raw blocks }}}` that don't implement [`RenderInto`] use this kind. With other
+/// escapers than the HTML escaper, values in `{{ value blocks }}` use it, too, and the generated
+/// code escapes the result.
+#[doc(hidden)]
+pub trait DisplayKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> &'a T {
+        value
+    }
+
+    #[inline]
+    fn wrap_raw<'a, T: fmt::Display>(&self, value: &'a T) -> &'a T {
+        value
+    }
+
+    /// Arguments of translated messages that are not integers or floats are strings
+    #[inline]
+    fn is_number(&self) -> bool {
+        false
+    }
+}
+
+/// A nested template that is rendered directly into the output of its parent
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct RenderWrapper<'a, T: ?Sized>(&'a T);
+
+/// A nested template that is rendered directly into the output of its parent with the render
+/// context of its parent
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct RenderWithWrapper<'a, T: ?Sized>(&'a T);
+
+/// Write a wrapped value into the output of a template
+#[doc(hidden)]
+pub trait Render {
+    fn render(&self, output: impl WriteAny) -> fmt::Result;
+}
+
+impl<T: fmt::Display + ?Sized> Render for T {
+    #[inline]
+    fn render(&self, mut output: impl WriteAny) -> fmt::Result {
+        output.write_fmt(format_args!("{}", self))
+    }
+}
+
+/// Prints a wrapped value, e.g. to use it as argument of a translated message
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct RenderDisplay<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Render + ?Sized> fmt::Display for RenderDisplay<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.render(WriteFmt(f))
+    }
+}
+
+impl<T: RenderInto + ?Sized> Render for RenderWrapper<'_, T> {
+    #[inline]
+    fn render(&self, mut output: impl WriteAny) -> fmt::Result {
+        // Erase the type of `output`, otherwise recursive templates could not be instantiated.
+        let output: &mut dyn WriteAny = &mut output;
+        self.0.render_into(output)
+    }
+}
+
+/// Write a wrapped value into the output of a template with the render context `C`
+///
+/// Nested templates with a render context receive the context, all other values are rendered
+/// with [`Render`]. A nested template that needs another context type does not compile.
+#[doc(hidden)]
+pub trait RenderContext<C: ?Sized> {
+    fn render_ctx(&self, ctx: &C, output: impl WriteAny) -> fmt::Result;
+}
+
+impl<T: Render + ?Sized, C: ?Sized> RenderContext<C> for T {
+    #[inline]
+    fn render_ctx(&self, _: &C, output: impl WriteAny) -> fmt::Result {
+        self.render(output)
+    }
+}
+
+impl<T: RenderWith + ?Sized> RenderContext<T::Context> for RenderWithWrapper<'_, T> {
+    #[inline]
+    fn render_ctx(&self, ctx: &T::Context, mut output: impl WriteAny) -> fmt::Result {
+        // Erase the type of `output`, otherwise recursive templates could not be instantiated.
+        let output: &mut dyn WriteAny = &mut output;
+        self.0.render_with_into(ctx, output)
+    }
+}
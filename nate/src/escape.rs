@@ -13,6 +13,12 @@ pub trait EscapeKind {
     fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> XmlEscape<&'a T> {
         XmlEscape(value)
     }
+
+    /// Escape a value that was formatted with a format specifier, e.g. `{{ value :>20 }}`
+    #[inline]
+    fn wrap_fmt<'a>(&self, formatted: fmt::Arguments<'a>) -> XmlEscape<fmt::Arguments<'a>> {
+        XmlEscape(formatted)
+    }
 }
 
 /// A wrapper around a [displayable][fmt::Display] type that makes it write out XML escaped.
@@ -51,20 +57,418 @@ const _: () = {
     };
 
     impl fmt::Write for XmlEscapeWriter<'_, '_> {
+        fn write_str(&mut self, string: &str) -> fmt::Result {
+            let mut last = 0;
+            while let Some(index) = find_special(string.as_bytes(), last) {
+                let byte = string.as_bytes()[index];
+                if let Some(escaped) = TABLE[(byte - MIN_CHAR) as usize] {
+                    self.0.write_str(&string[last..index])?;
+                    self.0.write_str(escaped)?;
+                }
+                last = index + 1;
+            }
+            self.0.write_str(&string[last..])
+        }
+    }
+
+    /// Tell if the byte needs to be escaped
+    #[inline]
+    fn is_special(byte: u8) -> bool {
+        // Bitwise or instead of `||` or `matches!()`, so the loop over a chunk can be vectorized.
+        (byte == b'"') | (byte == b'&') | (byte == b'\'') | (byte == b'<') | (byte == b'>')
+    }
+
+    const CHUNK: usize = 32;
+
+    /// Find the index of the next byte in `bytes[start..]` that needs to be escaped
+    ///
+    /// A whole chunk of bytes is tested at once without branches, which the compiler can turn
+    /// into SIMD instructions, until a chunk contains a byte that needs to be escaped.
+    #[inline]
+    fn find_special(bytes: &[u8], start: usize) -> Option<usize> {
+        let mut index = start;
+        while let Some(chunk) = bytes.get(index..index + CHUNK) {
+            let mut found = 0;
+            for &byte in chunk {
+                found |= is_special(byte) as u8;
+            }
+            if found != 0 {
+                break;
+            }
+            index += CHUNK;
+        }
+        let offset = bytes[index..].iter().position(|&byte| is_special(byte))?;
+        Some(index + offset)
+    }
+};
+
+impl<E: fmt::Display> UrlEscapeKind for &EscapeWrapper<E> {}
+
+impl<E: fmt::Display> UrlPathEscapeKind for &EscapeWrapper<E> {}
+
+impl<E: fmt::Display> UrlFormEscapeKind for &EscapeWrapper<E> {}
+
+#[doc(hidden)]
+pub trait UrlEscapeKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> UrlEscape<&'a T> {
+        UrlEscape(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait UrlPathEscapeKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> UrlPathEscape<&'a T> {
+        UrlPathEscape(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait UrlFormEscapeKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> UrlFormEscape<&'a T> {
+        UrlFormEscape(value)
+    }
+}
+
+/// A wrapper around a [displayable][fmt::Display] type that makes it write out percent-encoded
+/// to be used as a query component in a URL.
+///
+/// All characters are percent-encoded, e.g. `%26`, except for ASCII letters and digits,
+/// and ``!$()*,-./:;?@_~``. Use it for keys and values in a query string, e.g.
+/// `<a href="/search?q={{ self.query }}">` with `#[template(escape = "url")]`.
+///
+/// The output never contains XML special characters, so it can be used in HTML attributes.
+pub struct UrlEscape<T: ?Sized>(pub T);
+
+/// A wrapper around a [displayable][fmt::Display] type that makes it write out percent-encoded
+/// to be used as a path segment in a URL.
+///
+/// All characters are percent-encoded, e.g. `%2F`, except for ASCII letters and digits,
+/// and ``!$()*+,-.:;=@_~``. Slashes `/` are encoded, too.
+///
+/// The output never contains XML special characters, so it can be used in HTML attributes.
+pub struct UrlPathEscape<T: ?Sized>(pub T);
+
+/// A wrapper around a [displayable][fmt::Display] type that makes it write out
+/// `application/x-www-form-urlencoded`.
+///
+/// Spaces are written as `+`. All other characters are percent-encoded, e.g. `%2B`, except for
+/// ASCII letters and digits, and `*-._`.
+///
+/// The output never contains XML special characters, so it can be used in HTML attributes.
+pub struct UrlFormEscape<T: ?Sized>(pub T);
+
+const _: () = {
+    impl<T: ?Sized + fmt::Display> fmt::Display for UrlEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(UrlEscapeWriter(f, &QUERY, false), "{}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for UrlEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(UrlEscapeWriter(f, &QUERY, false), "{:?}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Display> fmt::Display for UrlPathEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(UrlEscapeWriter(f, &PATH, false), "{}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for UrlPathEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(UrlEscapeWriter(f, &PATH, false), "{:?}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Display> fmt::Display for UrlFormEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(UrlEscapeWriter(f, &FORM, true), "{}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for UrlFormEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(UrlEscapeWriter(f, &FORM, true), "{:?}", &self.0)
+        }
+    }
+
+    /// Writes its input percent-encoded. Only ASCII characters in the table are kept as is.
+    /// If the last field is `true`, spaces are written as `+`.
+    struct UrlEscapeWriter<'a, 'b>(&'a mut fmt::Formatter<'b>, &'static [bool; 128], bool);
+
+    const fn table(keep: &[u8]) -> [bool; 128] {
+        let mut table = [false; 128];
+        let mut c = 0;
+        while c < 128 {
+            table[c] = (c as u8).is_ascii_alphanumeric();
+            c += 1;
+        }
+        let mut i = 0;
+        while i < keep.len() {
+            table[keep[i] as usize] = true;
+            i += 1;
+        }
+        table
+    }
+
+    const QUERY: [bool; 128] = table(b"!$()*,-./:;?@_~");
+    const PATH: [bool; 128] = table(b"!$()*+,-.:;=@_~");
+    const FORM: [bool; 128] = table(b"*-._");
+
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    impl fmt::Write for UrlEscapeWriter<'_, '_> {
         fn write_str(&mut self, string: &str) -> fmt::Result {
             let mut last = 0;
             for (index, byte) in string.bytes().enumerate() {
-                let escaped = match byte {
-                    MIN_CHAR..=MAX_CHAR => TABLE[(byte - MIN_CHAR) as usize],
-                    _ => None,
-                };
-                if let Some(escaped) = escaped {
+                if byte < 128 && self.1[byte as usize] {
+                    continue;
+                }
+                // `last` can be inside of a multi-byte character if the run is empty.
+                if last < index {
                     self.0.write_str(&string[last..index])?;
+                }
+                if byte == b' ' && self.2 {
+                    self.0.write_char('+')?;
+                } else {
+                    self.0.write_char('%')?;
+                    self.0.write_char(HEX[(byte >> 4) as usize] as char)?;
+                    self.0.write_char(HEX[(byte & 15) as usize] as char)?;
+                }
+                last = index + 1;
+            }
+            match string.get(last..) {
+                Some(rest) if !rest.is_empty() => self.0.write_str(rest),
+                _ => Ok(()),
+            }
+        }
+    }
+};
+
+impl<E: fmt::Display> JsStringEscapeKind for &EscapeWrapper<E> {}
+
+impl<E: fmt::Display> CssStringEscapeKind for &EscapeWrapper<E> {}
+
+impl<E: fmt::Display> CssIdentEscapeKind for &EscapeWrapper<E> {}
+
+#[doc(hidden)]
+pub trait JsStringEscapeKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> JsStringEscape<&'a T> {
+        JsStringEscape(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait CssStringEscapeKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> CssStringEscape<&'a T> {
+        CssStringEscape(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait CssIdentEscapeKind {
+    #[inline]
+    fn wrap<'a, T: fmt::Display>(&self, value: &'a T) -> CssIdentEscape<&'a T> {
+        CssIdentEscape(value)
+    }
+}
+
+/// A wrapper around a [displayable][fmt::Display] type that makes it write out escaped to be
+/// used inside of a JavaScript string literal.
+///
+/// Backslashes, quotes, backticks, control characters, and the line separators U+2028 and U+2029
+/// are escaped, e.g. `\\`, `\x22`, or `\u2028`. `<`, `>`, and `&` are escaped, too, so the output
+/// cannot close a `<script>` element, e.g. with `</script>`.
+///
+/// The output never contains XML special characters, so it can be used in HTML attributes.
+pub struct JsStringEscape<T: ?Sized>(pub T);
+
+/// A wrapper around a [displayable][fmt::Display] type that makes it write out escaped to be
+/// used inside of a CSS string, e.g. `content: "{{ self.text }}"`.
+///
+/// Backslashes, quotes, control characters, `<`, `>`, and `&` are written as hexadecimal escape
+/// sequences, e.g. `\22 `.
+///
+/// The output never contains XML special characters, so it can be used in HTML attributes.
+pub struct CssStringEscape<T: ?Sized>(pub T);
+
+/// A wrapper around a [displayable][fmt::Display] type that makes it write out escaped to be
+/// used as a CSS identifier, e.g. a class name in a selector.
+///
+/// The value is escaped like [`CSS.escape()`](https://drafts.csswg.org/cssom/#the-css.escape()-method)
+/// would do it, except that XML special characters are written as hexadecimal escape sequences.
+/// Numbers are escaped, too, because an identifier cannot start with a digit, e.g. `42` is written
+/// as `\34 2`.
+///
+/// The output never contains XML special characters, so it can be used in HTML attributes.
+pub struct CssIdentEscape<T: ?Sized>(pub T);
+
+const _: () = {
+    impl<T: ?Sized + fmt::Display> fmt::Display for JsStringEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(JsStringEscapeWriter(f), "{}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for JsStringEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(JsStringEscapeWriter(f), "{:?}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Display> fmt::Display for CssStringEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(CssStringEscapeWriter(f), "{}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for CssStringEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(CssStringEscapeWriter(f), "{:?}", &self.0)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Display> fmt::Display for CssIdentEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut w = CssIdentEscapeWriter::new(f);
+            write!(w, "{}", &self.0)?;
+            w.finish()
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for CssIdentEscape<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut w = CssIdentEscapeWriter::new(f);
+            write!(w, "{:?}", &self.0)?;
+            w.finish()
+        }
+    }
+
+    struct JsStringEscapeWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+    impl fmt::Write for JsStringEscapeWriter<'_, '_> {
+        fn write_str(&mut self, string: &str) -> fmt::Result {
+            let mut last = 0;
+            for (index, c) in string.char_indices() {
+                let escaped = match c {
+                    '\\' => "\\\\",
+                    '\n' => "\\n",
+                    '\r' => "\\r",
+                    '\t' => "\\t",
+                    '\u{2028}' => "\\u2028",
+                    '\u{2029}' => "\\u2029",
+                    '"' | '\'' | '`' | '<' | '>' | '&' | '\0'..='\x1f' | '\x7f' => "",
+                    _ => continue,
+                };
+                self.0.write_str(&string[last..index])?;
+                if escaped.is_empty() {
+                    write!(self.0, "\\x{:02X}", c as u32)?;
+                } else {
                     self.0.write_str(escaped)?;
-                    last = index + 1;
                 }
+                last = index + c.len_utf8();
             }
             self.0.write_str(&string[last..])
         }
     }
+
+    struct CssStringEscapeWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+    impl fmt::Write for CssStringEscapeWriter<'_, '_> {
+        fn write_str(&mut self, string: &str) -> fmt::Result {
+            let mut last = 0;
+            for (index, byte) in string.bytes().enumerate() {
+                match byte {
+                    b'\\' | b'"' | b'\'' | b'<' | b'>' | b'&' | b'\0'..=b'\x1f' | b'\x7f' => {},
+                    _ => continue,
+                }
+                self.0.write_str(&string[last..index])?;
+                match byte {
+                    b'\0' => self.0.write_str("\\FFFD ")?,
+                    _ => write!(self.0, "\\{:X} ", byte)?,
+                }
+                last = index + 1;
+            }
+            self.0.write_str(&string[last..])
+        }
+    }
+
+    /// Escapes a CSS identifier. The writer has to know if a character is the first or second
+    /// character of the identifier. A leading dash is only written once the next character or
+    /// the end of the input is known.
+    struct CssIdentEscapeWriter<'a, 'b> {
+        f: &'a mut fmt::Formatter<'b>,
+        index: usize,
+        leading_dash: bool,
+    }
+
+    impl<'a, 'b> CssIdentEscapeWriter<'a, 'b> {
+        fn new(f: &'a mut fmt::Formatter<'b>) -> Self {
+            Self {
+                f,
+                index: 0,
+                leading_dash: false,
+            }
+        }
+
+        fn finish(self) -> fmt::Result {
+            if self.leading_dash && self.index == 1 {
+                self.f.write_str("\\-")
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl fmt::Write for CssIdentEscapeWriter<'_, '_> {
+        fn write_str(&mut self, string: &str) -> fmt::Result {
+            for c in string.chars() {
+                let index = self.index;
+                self.index += 1;
+                if index == 0 && c == '-' {
+                    self.leading_dash = true;
+                    continue;
+                } else if index == 1 && self.leading_dash {
+                    self.f.write_char('-')?;
+                }
+
+                match c {
+                    '\0' => self.f.write_char('\u{fffd}')?,
+                    '\x01'..='\x1f' | '\x7f' | '"' | '\'' | '<' | '>' | '&' => {
+                        write!(self.f, "\\{:X} ", c as u32)?;
+                    },
+                    '0'..='9' if index == 0 || (index == 1 && self.leading_dash) => {
+                        write!(self.f, "\\{:X} ", c as u32)?;
+                    },
+                    '-' | '_' | '0'..='9' | 'A'..='Z' | 'a'..='z' | '\u{80}'..='\u{10ffff}' => {
+                        self.f.write_char(c)?;
+                    },
+                    _ => {
+                        self.f.write_char('\\')?;
+                        self.f.write_char(c)?;
+                    },
+                }
+            }
+            Ok(())
+        }
+    }
 };
@@ -3,15 +3,20 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-use core::marker::Copy;
+use core::marker::{Copy, PhantomData};
 use core::{cell, fmt, num};
 
 use ryu::{Buffer, Float};
 
 use crate::details::EscapeWrapper;
+use crate::fmt::FloatFormat;
 
 impl<T: FloatMarker> FloatKind for &&EscapeWrapper<T> {}
 
+impl<T: FloatMarker> FloatValueKind for &FloatValue<T> {}
+
+impl<T> OtherValueKind for FloatValue<T> {}
+
 /// Types implementing this marker get printed using [`ryu`](ryu)
 pub trait FloatMarker {
     #[doc(hidden)]
@@ -19,6 +24,13 @@ pub trait FloatMarker {
 
     #[doc(hidden)]
     fn escape(&self) -> Self::Escaped;
+
+    #[doc(hidden)]
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        let _ = format;
+        self.escape()
+    }
 }
 
 impl<T: FloatMarker> FloatMarker for &T {
@@ -28,6 +40,11 @@ impl<T: FloatMarker> FloatMarker for &T {
     fn escape(&self) -> Self::Escaped {
         T::escape(*self)
     }
+
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        T::escape_with(*self, format)
+    }
 }
 
 #[doc(hidden)]
@@ -36,14 +53,61 @@ pub trait FloatKind {
     fn wrap<T: FloatMarker>(&self, value: &T) -> <T as FloatMarker>::Escaped {
         value.escape()
     }
+
+    /// Arguments of translated messages with this kind are numbers
+    #[inline]
+    fn is_number(&self) -> bool {
+        true
+    }
 }
 
-pub struct FloatEscape<T: Float + Copy>(T);
+/// Wrapper used to print the floats of a template with the setting `float(…)`
+///
+/// `(&&FloatValue::new(value)).format(value, &FORMAT)` selects [`FloatValueKind`] for floats,
+/// and [`OtherValueKind`] for all other values, which are returned unchanged.
+/// The result is wrapped with [`EscapeWrapper`] like any other value.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FloatValue<T>(PhantomData<T>);
+
+impl<T> FloatValue<T> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(_: &T) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait FloatValueKind {
+    #[inline]
+    fn format<T: FloatMarker>(
+        &self,
+        value: &T,
+        format: &'static FloatFormat,
+    ) -> <T as FloatMarker>::Escaped {
+        value.escape_with(format)
+    }
+}
+
+#[doc(hidden)]
+pub trait OtherValueKind {
+    #[inline]
+    fn format<'a, T>(&self, value: &'a T, _: &'static FloatFormat) -> &'a T {
+        value
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct FloatEscape<T: Float + Copy>(T, Option<&'static FloatFormat>);
 
 impl<T: Float + Copy> fmt::Display for FloatEscape<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(Buffer::new().format(self.0))
+        match self.1 {
+            Some(format) => format.write(Buffer::new().format(self.0), f),
+            None => f.write_str(Buffer::new().format(self.0)),
+        }
     }
 }
 
@@ -54,12 +118,27 @@ impl<T: Float + Copy> fmt::Debug for FloatEscape<T> {
     }
 }
 
+/// A float that is formatted already is printed as it is
+impl<T: Float + Copy> FloatMarker for FloatEscape<T> {
+    type Escaped = Self;
+
+    #[inline]
+    fn escape(&self) -> Self::Escaped {
+        *self
+    }
+}
+
 impl FloatMarker for f32 {
     type Escaped = FloatEscape<Self>;
 
     #[inline]
     fn escape(&self) -> Self::Escaped {
-        FloatEscape(*self)
+        FloatEscape(*self, None)
+    }
+
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        FloatEscape(*self, Some(format))
     }
 }
 
@@ -68,7 +147,12 @@ impl FloatMarker for f64 {
 
     #[inline]
     fn escape(&self) -> Self::Escaped {
-        FloatEscape(*self)
+        FloatEscape(*self, None)
+    }
+
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        FloatEscape(*self, Some(format))
     }
 }
 
@@ -80,6 +164,12 @@ impl<T: FloatMarker> FloatMarker for cell::Ref<'_, T> {
         let value: &T = self;
         value.escape()
     }
+
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        let value: &T = self;
+        value.escape_with(format)
+    }
 }
 
 impl<T: FloatMarker> FloatMarker for cell::RefMut<'_, T> {
@@ -90,6 +180,12 @@ impl<T: FloatMarker> FloatMarker for cell::RefMut<'_, T> {
         let value: &T = self;
         value.escape()
     }
+
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        let value: &T = self;
+        value.escape_with(format)
+    }
 }
 
 impl<T: FloatMarker> FloatMarker for num::Wrapping<T> {
@@ -99,6 +195,11 @@ impl<T: FloatMarker> FloatMarker for num::Wrapping<T> {
     fn escape(&self) -> Self::Escaped {
         self.0.escape()
     }
+
+    #[inline]
+    fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+        self.0.escape_with(format)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -112,6 +213,12 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 
     impl<T: FloatMarker> FloatMarker for alloc::boxed::Box<T> {
@@ -122,6 +229,12 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 
     impl<T: FloatMarker> FloatMarker for alloc::rc::Rc<T> {
@@ -132,6 +245,12 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 
     impl<T: FloatMarker> FloatMarker for alloc::sync::Arc<T> {
@@ -142,6 +261,12 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 };
 
@@ -158,6 +283,12 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 
     impl<T: FloatMarker> FloatMarker for sync::RwLockReadGuard<'_, T> {
@@ -168,6 +299,12 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 
     impl<T: FloatMarker> FloatMarker for sync::RwLockWriteGuard<'_, T> {
@@ -178,5 +315,11 @@ const _: () = {
             let value: &T = self;
             value.escape()
         }
+
+        #[inline]
+        fn escape_with(&self, format: &'static FloatFormat) -> Self::Escaped {
+            let value: &T = self;
+            value.escape_with(format)
+        }
     }
 };
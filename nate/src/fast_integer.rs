@@ -36,6 +36,12 @@ pub trait IntKind {
     fn wrap<T: IntMarker>(&self, value: &T) -> <T as IntMarker>::Escaped {
         value.escape()
     }
+
+    /// Arguments of translated messages with this kind are numbers
+    #[inline]
+    fn is_number(&self) -> bool {
+        true
+    }
 }
 
 pub struct ItoaEscape<T: Integer + Copy>(T);
@@ -0,0 +1,742 @@
+//! Filters that can be used in value blocks, e.g. `{{ self.name | upper }}`
+//!
+//! A filter is a function that takes the value as its first argument, and returns something
+//! [displayable][fmt::Display]. Filters are applied before the value gets escaped.
+//! `{{ value | a | b(1, 2) }}` is the same as `{{ b(a(&value), 1, 2) }}`.
+//!
+//! The names of the functions in this module always refer to these filters, so you can write
+//! `upper` instead of `nate::filters::upper`. Your own filters are looked up like any other
+//! function, e.g. `{{ self.text | crate::markdown::render }}`. A filter in your own module that has
+//! the same name as a filter in this module has to be qualified, e.g. `self::upper`.
+//!
+//! A `|` that is followed by a name starts a filter. Put the bitwise or operator in parentheses if
+//! a name follows it, e.g. `{{ (self.flags | MASK) }}`. A filter that is not a path followed by
+//! optional arguments is an error.
+//!
+//! The special filter `safe` is not a function, but it turns the value block into a
+//! `{{{ raw block }}}`, so the value is printed verbatim.
+//!
+//! The special filter `e("…")` (or `escape("…")`) has to be the last filter. It selects the
+//! escaper of the value block, e.g. `{{ self.query | e("url") }}`. Valid names are the same as in
+//! `#[template(escape = "…")]`: `"html"`, `"url"`, `"url_path"`, `"url_form"`, `"js"`, `"css"`,
+//! and `"css_ident"`. The names `e` and `escape` are reserved, so a filter function of your own
+//! with one of these names has to be qualified, e.g. `self::e`. A value block with `e("…")` cannot
+//! have a format specifier, and it cannot be combined with `safe`.
+//!
+//! The filters don't allocate. They can be used as plain [`Display`][fmt::Display] adaptors
+//! outside of templates, too. They work on the unescaped text, so if you combine them manually
+//! with [`XmlEscape`], the escaper should be the outermost wrapper,
+//! e.g. `XmlEscape(truncate(&text, 20, "…"))`.
+//!
+//! If a width is given in the format specifier, e.g. `{{ self.name | upper :>20 }}`, then the
+//! output is padded accordingly.
+//!
+//! Filters that need to know the length of their output before printing it, i.e. [`truncate()`],
+//! [`center()`], [`ljust()`], [`rjust()`], [`trim()`], and every filter with a width in the format
+//! specifier, format the value twice: once to measure it, and once to print it.
+//!
+//! [`linebreaks()`] escapes its value itself and returns markup, so it has to be the last filter in
+//! a `{{ value block }}`.
+
+use core::fmt::{self, Alignment, Write as _};
+
+use crate::details::XmlEscape;
+use crate::RawMarker;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::fmt::{Date, DateFormat, DateTimeValue, Relative};
+
+/// Print the value in upper case
+#[inline]
+pub fn upper<T: fmt::Display>(value: T) -> Upper<T> {
+    Upper(value)
+}
+
+/// Print the value in lower case
+#[inline]
+pub fn lower<T: fmt::Display>(value: T) -> Lower<T> {
+    Lower(value)
+}
+
+/// Print the value in title case
+///
+/// The first letter of every word is printed in upper case, all other letters in lower case.
+/// A word starts after a whitespace, or one of `-`, `(`, `[`, `{`, `<`.
+#[inline]
+pub fn title<T: fmt::Display>(value: T) -> Title<T> {
+    Title(value)
+}
+
+/// Print at most `len` characters of the value
+///
+/// If the value is longer than `len` characters, then it is cut, so that it fits into `len`
+/// characters together with `end`, e.g. `truncate(&text, 20, "…")`. If `end` is longer than `len`
+/// characters, then only the first `len` characters of `end` are printed.
+///
+/// With the feature `unicode-segmentation`, extended grapheme clusters are counted instead of
+/// characters. Please notice that a cluster is counted twice, if the [`Display`][fmt::Display]
+/// implementation of the value writes it in two pieces.
+#[inline]
+pub fn truncate<T: fmt::Display>(value: T, len: usize, end: &str) -> Truncate<'_, T> {
+    Truncate { value, len, end }
+}
+
+/// Indent every line of the value except for the first one with `width` spaces
+///
+/// Empty lines are not indented.
+#[inline]
+pub fn indent<T: fmt::Display>(value: T, width: usize) -> Indent<T> {
+    Indent { value, width }
+}
+
+/// Wrap the words of the value, so that lines are at most `width` characters long
+///
+/// Runs of whitespaces between words are replaced with a single space, or a newline if the next
+/// word does not fit into the current line. Newlines in the input are kept. Words that are
+/// longer than `width` are not split.
+///
+/// To wrap a word it is buffered on the stack. Words longer than 128 bytes may overflow the
+/// current line.
+#[inline]
+pub fn wordwrap<T: fmt::Display>(value: T, width: usize) -> WordWrap<T> {
+    WordWrap { value, width }
+}
+
+/// Center the value in a field of `width` characters
+#[inline]
+pub fn center<T: fmt::Display>(value: T, width: usize) -> Justify<T> {
+    Justify {
+        value,
+        width,
+        align: Alignment::Center,
+    }
+}
+
+/// Align the value to the left of a field of `width` characters
+#[inline]
+pub fn ljust<T: fmt::Display>(value: T, width: usize) -> Justify<T> {
+    Justify {
+        value,
+        width,
+        align: Alignment::Left,
+    }
+}
+
+/// Align the value to the right of a field of `width` characters
+#[inline]
+pub fn rjust<T: fmt::Display>(value: T, width: usize) -> Justify<T> {
+    Justify {
+        value,
+        width,
+        align: Alignment::Right,
+    }
+}
+
+/// Remove leading and trailing whitespaces of the value
+#[inline]
+pub fn trim<T: fmt::Display>(value: T) -> Trim<T> {
+    Trim(value)
+}
+
+/// XML escape the value, and add a `<br>` in front of every newline
+///
+/// Because the value is escaped already, the result is printed verbatim in `{{ value blocks }}`.
+/// Other filters would treat the markup as text, so `linebreaks` has to be the last filter.
+#[inline]
+pub fn linebreaks<T: fmt::Display>(value: T) -> Linebreaks<T> {
+    Linebreaks(value)
+}
+
+/// Print a date or time value using a format description, e.g. `date("[year]-[month]-[day]")`
+///
+/// In templates a string literal is checked at compile time. See [`DateFormat`] for the syntax.
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "chrono", feature = "time"))))]
+#[inline]
+pub fn date<T: DateTimeValue>(value: T, format: DateFormat) -> Date<T> {
+    Date(value, format)
+}
+
+/// Print how long ago a date or time value is, e.g. `3 hours ago`, see [`Relative`]
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "chrono", feature = "time"))))]
+#[inline]
+pub fn relative<T: DateTimeValue>(value: T) -> Relative<T> {
+    Relative::new(value)
+}
+
+/// The path of a filter in a value block, e.g. `__nate_filter!(upper)`
+///
+/// The names of the functions in this module resolve to these functions, all other filters are
+/// returned as they are, and are looked up in the scope of the template.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nate_filter {
+    (center) => { $crate::filters::center };
+    (date) => { $crate::__nate_date_filter!(date) };
+    (indent) => { $crate::filters::indent };
+    (linebreaks) => { $crate::filters::linebreaks };
+    (ljust) => { $crate::filters::ljust };
+    (lower) => { $crate::filters::lower };
+    (relative) => { $crate::__nate_date_filter!(relative) };
+    (rjust) => { $crate::filters::rjust };
+    (title) => { $crate::filters::title };
+    (trim) => { $crate::filters::trim };
+    (truncate) => { $crate::filters::truncate };
+    (upper) => { $crate::filters::upper };
+    (wordwrap) => { $crate::filters::wordwrap };
+    ($($path:tt)*) => { $($path)* };
+}
+
+/// The filters `date` and `relative`, and `date(value, "format")` with a string literal, whose
+/// format description is checked at compile time
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nate_date_filter {
+    (date, $value:expr, $format:literal) => {
+        $crate::filters::date($value, {
+            const FORMAT: $crate::fmt::DateFormat = $crate::fmt::DateFormat::new($format);
+            FORMAT
+        })
+    };
+    ($name:ident) => { $crate::filters::$name };
+}
+
+/// Without the features `chrono` and `time` there are no date filters in this module
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nate_date_filter {
+    (date, $value:expr, $format:literal) => { date($value, $format) };
+    ($name:ident) => { $name };
+}
+
+/// Prints its value in upper case, see [`upper()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Upper<T>(pub T);
+
+/// Prints its value in lower case, see [`lower()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Lower<T>(pub T);
+
+/// Prints its value in title case, see [`title()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Title<T>(pub T);
+
+/// Prints at most `len` characters of its value, see [`truncate()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Truncate<'a, T> {
+    /// The value to print
+    pub value: T,
+    /// The maximum length of the output
+    pub len: usize,
+    /// Printed after the value if it was truncated
+    pub end: &'a str,
+}
+
+/// Indents every line of its value, see [`indent()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Indent<T> {
+    /// The value to print
+    pub value: T,
+    /// The number of spaces in front of every line
+    pub width: usize,
+}
+
+/// Wraps the words of its value, see [`wordwrap()`]
+#[derive(Debug, Clone, Copy)]
+pub struct WordWrap<T> {
+    /// The value to print
+    pub value: T,
+    /// The maximum length of a line
+    pub width: usize,
+}
+
+/// Pads its value, see [`center()`], [`ljust()`] and [`rjust()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Justify<T> {
+    /// The value to print
+    pub value: T,
+    /// The minimum length of the output
+    pub width: usize,
+    /// Where to put the value in the padded output
+    pub align: Alignment,
+}
+
+/// Removes leading and trailing whitespaces, see [`trim()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Trim<T>(pub T);
+
+/// Escapes its value and adds `<br>` in front of newlines, see [`linebreaks()`]
+#[derive(Debug, Clone, Copy)]
+pub struct Linebreaks<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for Upper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad(f, |w| write!(MapChars(w, char::to_uppercase), "{}", self.0))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Lower<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad(f, |w| write!(MapChars(w, char::to_lowercase), "{}", self.0))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Title<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad(f, |w| {
+            let mut w = TitleWriter {
+                output: w,
+                word_start: true,
+            };
+            write!(w, "{}", self.0)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Truncate<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut count = Count::new(self.len, units);
+        if write!(count, "{}", self.value).is_ok() && count.count <= self.len {
+            return pad(f, |w| write!(w, "{}", self.value));
+        }
+
+        let end_len = units(self.end);
+        if end_len > self.len {
+            // not even `end` fits, so only its start is printed
+            return pad(f, |w| {
+                let mut w = Slice {
+                    output: w,
+                    index: 0,
+                    start: 0,
+                    end: self.len,
+                    units,
+                    split_at: split_at_units,
+                };
+                w.write_str(self.end)
+            });
+        }
+
+        let len = self.len - end_len;
+        pad(f, |w| {
+            let mut w = Slice {
+                output: w,
+                index: 0,
+                start: 0,
+                end: len,
+                units,
+                split_at: split_at_units,
+            };
+            write!(w, "{}", self.value)?;
+            w.output.write_str(self.end)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Indent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad(f, |w| {
+            let mut w = IndentWriter {
+                output: w,
+                width: self.width,
+                line_start: false,
+            };
+            write!(w, "{}", self.value)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for WordWrap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad(f, |w| {
+            let mut w = WordWrapWriter {
+                output: w,
+                width: self.width,
+                column: 0,
+                word: [0; WORD_LEN],
+                word_len: 0,
+                word_chars: 0,
+                overflow: false,
+            };
+            write!(w, "{}", self.value)?;
+            w.flush_word()
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Justify<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_with(f, self.width, self.align, ' ', |w| {
+            write!(w, "{}", self.value)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Trim<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut scan = TrimScanner {
+            index: 0,
+            start: None,
+            end: 0,
+        };
+        write!(scan, "{}", self.0)?;
+        let (start, end) = match scan.start {
+            Some(start) => (start, scan.end),
+            None => return pad(f, |_| Ok(())),
+        };
+        pad(f, |w| {
+            let mut w = Slice {
+                output: w,
+                index: 0,
+                start,
+                end,
+                units: chars,
+                split_at: split_at_chars,
+            };
+            write!(w, "{}", self.0)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Linebreaks<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad(f, |w| {
+            let mut w = LinebreaksWriter {
+                output: w,
+                cr: false,
+            };
+            write!(w, "{}", XmlEscape(&self.0))?;
+            w.flush_cr()
+        })
+    }
+}
+
+impl<T: fmt::Display> RawMarker for Linebreaks<T> {}
+
+/// Write the output of `write` into `f`, padded according to the width and alignment of `f`
+fn pad(
+    f: &mut fmt::Formatter<'_>,
+    write: impl Fn(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    match f.width() {
+        Some(width) => {
+            let align = f.align().unwrap_or(Alignment::Left);
+            let fill = f.fill();
+            pad_with(f, width, align, fill, write)
+        },
+        None => write(f),
+    }
+}
+
+/// Write the output of `write` into `f`, padded to at least `width` characters
+pub(crate) fn pad_with(
+    f: &mut fmt::Formatter<'_>,
+    width: usize,
+    align: Alignment,
+    fill: char,
+    write: impl Fn(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let mut count = Count::new(width, chars);
+    let padding = match write(&mut count) {
+        Ok(()) if count.count < width => width - count.count,
+        _ => return write(f),
+    };
+    let (before, after) = match align {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    write(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+fn chars(s: &str) -> usize {
+    s.chars().count()
+}
+
+fn split_at_chars(s: &str, count: usize) -> usize {
+    s.char_indices()
+        .nth(count)
+        .map_or(s.len(), |(index, _)| index)
+}
+
+#[cfg(not(feature = "unicode-segmentation"))]
+use {chars as units, split_at_chars as split_at_units};
+
+#[cfg(feature = "unicode-segmentation")]
+fn units(s: &str) -> usize {
+    unicode_segmentation_crate::UnicodeSegmentation::graphemes(s, true).count()
+}
+
+#[cfg(feature = "unicode-segmentation")]
+fn split_at_units(s: &str, count: usize) -> usize {
+    unicode_segmentation_crate::UnicodeSegmentation::grapheme_indices(s, true)
+        .nth(count)
+        .map_or(s.len(), |(index, _)| index)
+}
+
+/// Counts the written characters, and fails once more than `limit` characters were written
+struct Count {
+    count: usize,
+    limit: usize,
+    units: fn(&str) -> usize,
+}
+
+impl Count {
+    fn new(limit: usize, units: fn(&str) -> usize) -> Self {
+        Self {
+            count: 0,
+            limit,
+            units,
+        }
+    }
+}
+
+impl fmt::Write for Count {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.count += (self.units)(s);
+        if self.count <= self.limit {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+/// Writes only the characters in `start..end` of its input
+struct Slice<'a> {
+    output: &'a mut dyn fmt::Write,
+    index: usize,
+    start: usize,
+    end: usize,
+    units: fn(&str) -> usize,
+    split_at: fn(&str, usize) -> usize,
+}
+
+impl fmt::Write for Slice<'_> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        if self.index < self.start {
+            let skip = (self.split_at)(s, self.start - self.index);
+            self.index += (self.units)(&s[..skip]);
+            s = &s[skip..];
+        }
+        if self.index >= self.start && self.index < self.end {
+            let s = &s[..(self.split_at)(s, self.end - self.index)];
+            self.index += (self.units)(s);
+            self.output.write_str(s)?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the index of the first and last non-whitespace character
+struct TrimScanner {
+    index: usize,
+    start: Option<usize>,
+    end: usize,
+}
+
+impl fmt::Write for TrimScanner {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.index += 1;
+            if !c.is_whitespace() {
+                if self.start.is_none() {
+                    self.start = Some(self.index - 1);
+                }
+                self.end = self.index;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes every character of its input as the characters returned by a function
+struct MapChars<'a, F>(&'a mut dyn fmt::Write, F);
+
+impl<F, I> fmt::Write for MapChars<'_, F>
+where
+    F: Fn(char) -> I,
+    I: Iterator<Item = char>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            for c in (self.1)(c) {
+                self.0.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct TitleWriter<'a> {
+    output: &'a mut dyn fmt::Write,
+    word_start: bool,
+}
+
+impl fmt::Write for TitleWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c.is_whitespace() || matches!(c, '-' | '(' | '[' | '{' | '<') {
+                self.output.write_char(c)?;
+                self.word_start = true;
+            } else if self.word_start {
+                for c in c.to_uppercase() {
+                    self.output.write_char(c)?;
+                }
+                self.word_start = false;
+            } else {
+                for c in c.to_lowercase() {
+                    self.output.write_char(c)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct IndentWriter<'a> {
+    output: &'a mut dyn fmt::Write,
+    width: usize,
+    line_start: bool,
+}
+
+impl fmt::Write for IndentWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.line_start && c != '\n' && c != '\r' {
+                for _ in 0..self.width {
+                    self.output.write_char(' ')?;
+                }
+            }
+            self.line_start = c == '\n';
+            self.output.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+const WORD_LEN: usize = 128;
+
+struct WordWrapWriter<'a> {
+    output: &'a mut dyn fmt::Write,
+    width: usize,
+    column: usize,
+    word: [u8; WORD_LEN],
+    word_len: usize,
+    word_chars: usize,
+    overflow: bool,
+}
+
+impl WordWrapWriter<'_> {
+    /// Write a space or a newline in front of a word with `len` characters
+    fn start_word(&mut self, len: usize) -> fmt::Result {
+        if self.column > 0 {
+            if self.column + 1 + len > self.width {
+                self.output.write_char('\n')?;
+                self.column = 0;
+            } else {
+                self.output.write_char(' ')?;
+                self.column += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_buffer(&mut self) -> fmt::Result {
+        // The buffer only contains whole characters.
+        let word = core::str::from_utf8(&self.word[..self.word_len]).map_err(|_| fmt::Error)?;
+        self.output.write_str(word)?;
+        self.column += self.word_chars;
+        self.word_len = 0;
+        self.word_chars = 0;
+        Ok(())
+    }
+
+    fn flush_word(&mut self) -> fmt::Result {
+        if self.overflow {
+            self.overflow = false;
+        } else if self.word_chars > 0 {
+            self.start_word(self.word_chars)?;
+            self.write_buffer()?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for WordWrapWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '\n' {
+                self.flush_word()?;
+                self.output.write_char('\n')?;
+                self.column = 0;
+            } else if c.is_whitespace() {
+                self.flush_word()?;
+            } else if self.overflow {
+                self.output.write_char(c)?;
+                self.column += 1;
+            } else if self.word_len + c.len_utf8() <= WORD_LEN {
+                let len = c.encode_utf8(&mut self.word[self.word_len..]).len();
+                self.word_len += len;
+                self.word_chars += 1;
+            } else {
+                self.start_word(self.word_chars + 1)?;
+                self.write_buffer()?;
+                self.output.write_char(c)?;
+                self.column += 1;
+                self.overflow = true;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct LinebreaksWriter<'a> {
+    output: &'a mut dyn fmt::Write,
+    /// The preceding chunk ended with a `\r`, which is dropped if the next chunk starts with `\n`
+    cr: bool,
+}
+
+impl LinebreaksWriter<'_> {
+    /// Write the pending `\r`, which was not followed by a `\n`
+    fn flush_cr(&mut self) -> fmt::Result {
+        if self.cr {
+            self.cr = false;
+            self.output.write_char('\r')?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for LinebreaksWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        let mut line = lines.next().unwrap_or("");
+        if !line.is_empty() {
+            self.flush_cr()?;
+        }
+        for next in lines {
+            self.cr = false;
+            self.output
+                .write_str(line.strip_suffix('\r').unwrap_or(line))?;
+            self.output.write_str("<br>\n")?;
+            line = next;
+        }
+        match line.strip_suffix('\r') {
+            Some(line) => {
+                self.output.write_str(line)?;
+                self.cr = true;
+            },
+            None => self.output.write_str(line)?,
+        }
+        Ok(())
+    }
+}
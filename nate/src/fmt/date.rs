@@ -0,0 +1,707 @@
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::fmt;
+
+/// Print a date or time value using a [`DateFormat`]
+///
+/// ```rust
+/// # #[cfg(feature = "time")] {
+/// use nate::fmt::{Date, DateFormat};
+///
+/// const FORMAT: DateFormat = DateFormat::new("[day]. [month repr:long] [year], [hour]:[minute]");
+///
+/// let created = time::Date::from_calendar_date(2022, time::Month::May, 10)
+///     .unwrap()
+///     .with_hms(13, 37, 0)
+///     .unwrap();
+/// assert_eq!(Date(&created, FORMAT).to_string(), "10. May 2022, 13:37");
+/// # }
+/// ```
+///
+/// In templates you can use the filter [`date`][crate::filters::date] instead, e.g.
+/// `{{ self.created | date("[year]-[month]-[day]") }}`.
+#[derive(Debug, Clone, Copy)]
+pub struct Date<T>(pub T, pub DateFormat);
+
+impl<T: DateTimeValue> fmt::Display for Date<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = self.0.parts();
+        let format = self.1.0.as_bytes();
+        let mut pos = 0;
+        while pos < format.len() {
+            let (item, next) = match parse_item(format, pos) {
+                Ok(result) => result,
+                Err(_) => return Err(fmt::Error),
+            };
+            write_item(f, &self.1.0[pos..next], item, &parts)?;
+            pos = next;
+        }
+        Ok(())
+    }
+}
+
+/// A format description like `"[year]-[month]-[day] [hour]:[minute]:[second]"`
+///
+/// The syntax is a subset of the format descriptions of the crate
+/// [`time`](https://time-rs.github.io/book/api/format-description.html).
+/// All text outside of brackets is printed verbatim, `[[` prints a single `[`.
+///
+/// | Component        | Output       | Modifiers                                                   |
+/// |------------------|--------------|-------------------------------------------------------------|
+/// | `[year]`         | `2022`       | `repr:full`, `repr:last_two`                                |
+/// | `[month]`        | `05`         | `repr:numerical`, `repr:short` (`May`), `repr:long`, `padding` |
+/// | `[day]`          | `09`         | `padding`                                                   |
+/// | `[ordinal]`      | `129`        | `padding`                                                   |
+/// | `[weekday]`      | `Monday`     | `repr:long`, `repr:short` (`Mon`)                           |
+/// | `[hour]`         | `13`         | `repr:24`, `repr:12`, `padding`                             |
+/// | `[minute]`       | `37`         | `padding`                                                   |
+/// | `[second]`       | `05`         | `padding`                                                   |
+/// | `[subsecond]`    | `25`         | `digits:1` … `digits:9`, `digits:one_or_more`               |
+/// | `[period]`       | `PM`         | `case:upper`, `case:lower`                                  |
+/// | `[offset_hour]`  | `-05`        | `sign:automatic`, `sign:mandatory`                          |
+/// | `[offset_minute]`| `30`         |                                                             |
+/// | `[unix_timestamp]` | `1652189820` |                                                           |
+///
+/// The modifier `padding` can be `padding:zero` (the default), `padding:space`, or
+/// `padding:none`. Names of months and weekdays are English.
+///
+/// A `DateFormat` should be created in a `const` context, then an invalid format description is
+/// a compile time error:
+///
+/// ```compile_fail
+/// # #[cfg(not(any(feature = "chrono", feature = "time")))] compile_error!("dummy");
+/// const FORMAT: nate::fmt::DateFormat = nate::fmt::DateFormat::new("[yaer]");
+/// # let _ = FORMAT;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateFormat(&'static str);
+
+/// Indexed by [`DateFormat::new()`] if the format description is invalid
+const INVALID_FORMAT_DESCRIPTION: [usize; 0] = [];
+
+impl DateFormat {
+    /// Check the format description
+    ///
+    /// # Panics
+    ///
+    /// Panics if the format description is invalid.
+    pub const fn new(format: &'static str) -> Self {
+        let bytes = format.as_bytes();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            pos = match parse_item(bytes, pos) {
+                Ok((_, next)) => next,
+                // `panic!()` in a `const fn` needs Rust 1.57, but an index out of bounds is an
+                // error in a `const` context, too
+                Err(_) => INVALID_FORMAT_DESCRIPTION[pos],
+            };
+        }
+        Self(format)
+    }
+
+    /// The format description
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+/// Print how long ago, or how far in the future a date is, e.g. `3 hours ago` or `in 2 days`
+///
+/// ```rust
+/// # #[cfg(feature = "time")] {
+/// use nate::fmt::Relative;
+/// use time::{Duration, OffsetDateTime};
+///
+/// let now = OffsetDateTime::UNIX_EPOCH + Duration::days(20_000);
+/// let then = now - Duration::hours(3);
+/// assert_eq!(Relative::new(then).now(now).to_string(), "3 hours ago");
+/// # }
+/// ```
+///
+/// The output is English. Differences are rounded to the nearest unit, differences of less than
+/// 45 seconds are printed as `just now`.
+///
+/// Without the feature `std` the current time is unknown, so you have to supply it with
+/// [`Relative::now()`], otherwise printing the value fails.
+#[derive(Debug, Clone, Copy)]
+pub struct Relative<T> {
+    value: T,
+    now: Option<i64>,
+}
+
+impl<T: DateTimeValue> Relative<T> {
+    /// Print the value relative to the current time
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self { value, now: None }
+    }
+
+    /// Print the value relative to `now` instead of the current time
+    #[inline]
+    pub fn now<U: DateTimeValue>(mut self, now: U) -> Self {
+        self.now = Some(now.parts().unix_timestamp);
+        self
+    }
+}
+
+impl<T: DateTimeValue> fmt::Display for Relative<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: &[(&str, i64, i64)] = &[
+            // (name, seconds, print this unit if less than this many seconds)
+            ("minute", 60, 45 * 60),
+            ("hour", 60 * 60, 22 * 60 * 60),
+            ("day", 24 * 60 * 60, 26 * 24 * 60 * 60),
+            ("month", 2_629_746, 320 * 24 * 60 * 60),
+            ("year", 31_556_952, i64::MAX),
+        ];
+
+        let now = match self.now {
+            Some(now) => now,
+            None => current_timestamp()?,
+        };
+        let then = self.value.parts().unix_timestamp;
+        let seconds = now.saturating_sub(then);
+        let abs = seconds.saturating_abs();
+        if abs < 45 {
+            return f.write_str("just now");
+        }
+
+        let (name, count) = UNITS
+            .iter()
+            .find(|&&(_, _, limit)| abs < limit)
+            .map(|&(name, unit, _)| (name, (abs.saturating_add(unit / 2) / unit).max(1)))
+            .unwrap_or(("year", abs / 31_556_952));
+        let plural = if count == 1 { "" } else { "s" };
+        let mut buf = itoa::Buffer::new();
+        let count = buf.format(count);
+        if seconds >= 0 {
+            write!(f, "{} {}{} ago", count, name, plural)
+        } else {
+            write!(f, "in {} {}{}", count, name, plural)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn current_timestamp() -> Result<i64, fmt::Error> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => Ok(duration.as_secs() as i64),
+        Err(err) => Ok(-(err.duration().as_secs() as i64)),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn current_timestamp() -> Result<i64, fmt::Error> {
+    Err(fmt::Error)
+}
+
+/// A date or time value that can be printed with [`Date`] or [`Relative`]
+pub trait DateTimeValue {
+    #[doc(hidden)]
+    fn parts(&self) -> DateTimeParts;
+}
+
+impl<T: DateTimeValue + ?Sized> DateTimeValue for &T {
+    #[inline]
+    fn parts(&self) -> DateTimeParts {
+        T::parts(*self)
+    }
+}
+
+/// The components of a date and time value
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct DateTimeParts {
+    year: i32,
+    /// 1 to 12
+    month: u8,
+    /// 1 to 31
+    day: u8,
+    /// 1 to 366
+    ordinal: u16,
+    /// 0 is Monday
+    weekday: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    /// offset to UTC in seconds
+    offset: i32,
+    unix_timestamp: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Padding {
+    Zero,
+    Space,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Item {
+    Literal,
+    Bracket,
+    Year { last_two: bool },
+    Month { repr: u8, padding: Padding },
+    Day(Padding),
+    Ordinal(Padding),
+    Weekday { short: bool },
+    Hour { twelve: bool, padding: Padding },
+    Minute(Padding),
+    Second(Padding),
+    Subsecond { digits: u8 },
+    Period { lower: bool },
+    OffsetHour { mandatory: bool },
+    OffsetMinute,
+    UnixTimestamp,
+}
+
+const MONTH_NUMERICAL: u8 = 0;
+const MONTH_SHORT: u8 = 1;
+const MONTH_LONG: u8 = 2;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+fn write_item(
+    f: &mut fmt::Formatter<'_>,
+    text: &str,
+    item: Item,
+    parts: &DateTimeParts,
+) -> fmt::Result {
+    match item {
+        Item::Literal => f.write_str(text),
+        Item::Bracket => f.write_str("["),
+        Item::Year { last_two: true } => {
+            write_number(f, parts.year.rem_euclid(100) as u32, 2, Padding::Zero)
+        },
+        Item::Year { last_two: false } => {
+            if parts.year < 0 {
+                f.write_str("-")?;
+            }
+            write_number(f, parts.year.unsigned_abs(), 4, Padding::Zero)
+        },
+        Item::Month { repr, padding } => {
+            let name = MONTHS[(parts.month.clamp(1, 12) - 1) as usize];
+            match repr {
+                MONTH_SHORT => f.write_str(&name[..3]),
+                MONTH_LONG => f.write_str(name),
+                _ => write_number(f, parts.month.into(), 2, padding),
+            }
+        },
+        Item::Day(padding) => write_number(f, parts.day.into(), 2, padding),
+        Item::Ordinal(padding) => write_number(f, parts.ordinal.into(), 3, padding),
+        Item::Weekday { short } => {
+            let name = WEEKDAYS[parts.weekday.min(6) as usize];
+            f.write_str(if short { &name[..3] } else { name })
+        },
+        Item::Hour {
+            twelve: true,
+            padding,
+        } => {
+            let hour = match parts.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            write_number(f, hour.into(), 2, padding)
+        },
+        Item::Hour {
+            twelve: false,
+            padding,
+        } => write_number(f, parts.hour.into(), 2, padding),
+        Item::Minute(padding) => write_number(f, parts.minute.into(), 2, padding),
+        Item::Second(padding) => write_number(f, parts.second.into(), 2, padding),
+        Item::Subsecond { digits: 0 } => {
+            let mut nanos = parts.nanosecond;
+            let mut digits = 9;
+            while digits > 1 && nanos % 10 == 0 {
+                nanos /= 10;
+                digits -= 1;
+            }
+            write_number(f, nanos, digits, Padding::Zero)
+        },
+        Item::Subsecond { digits } => {
+            let nanos = parts.nanosecond / 10_u32.pow(9 - u32::from(digits));
+            write_number(f, nanos, digits.into(), Padding::Zero)
+        },
+        Item::Period { lower } => f.write_str(match (parts.hour < 12, lower) {
+            (true, false) => "AM",
+            (true, true) => "am",
+            (false, false) => "PM",
+            (false, true) => "pm",
+        }),
+        Item::OffsetHour { mandatory } => {
+            if parts.offset < 0 {
+                f.write_str("-")?;
+            } else if mandatory {
+                f.write_str("+")?;
+            }
+            write_number(f, parts.offset.unsigned_abs() / 3600, 2, Padding::Zero)
+        },
+        Item::OffsetMinute => {
+            let minutes = parts.offset.unsigned_abs() / 60 % 60;
+            write_number(f, minutes, 2, Padding::Zero)
+        },
+        Item::UnixTimestamp => f.write_str(itoa::Buffer::new().format(parts.unix_timestamp)),
+    }
+}
+
+fn write_number(
+    f: &mut fmt::Formatter<'_>,
+    value: u32,
+    width: usize,
+    padding: Padding,
+) -> fmt::Result {
+    let mut buf = itoa::Buffer::new();
+    let s = buf.format(value);
+    let fill = match padding {
+        Padding::Zero => "0",
+        Padding::Space => " ",
+        Padding::None => "",
+    };
+    for _ in s.len()..width {
+        f.write_str(fill)?;
+    }
+    f.write_str(s)
+}
+
+/// Parse the item starting at `pos`, and return it together with the start of the next item
+const fn parse_item(s: &[u8], mut pos: usize) -> Result<(Item, usize), &'static str> {
+    if s[pos] != b'[' {
+        while pos < s.len() && s[pos] != b'[' {
+            pos += 1;
+        }
+        return Ok((Item::Literal, pos));
+    } else if pos + 1 < s.len() && s[pos + 1] == b'[' {
+        return Ok((Item::Bracket, pos + 2));
+    }
+
+    pos = skip_whitespace(s, pos + 1);
+    let start = pos;
+    pos = skip_word(s, pos);
+    let mut item = match component(s, start, pos) {
+        Some(item) => item,
+        None => return Err("date format: unknown component"),
+    };
+    loop {
+        pos = skip_whitespace(s, pos);
+        if pos >= s.len() {
+            return Err("date format: unclosed `[`");
+        } else if s[pos] == b']' {
+            return Ok((item, pos + 1));
+        }
+
+        let key = pos;
+        while pos < s.len() && s[pos] != b':' && s[pos] != b']' && !is_whitespace(s[pos]) {
+            pos += 1;
+        }
+        if pos >= s.len() || s[pos] != b':' {
+            return Err("date format: expected `modifier:value`");
+        }
+        let value = pos + 1;
+        pos = skip_word(s, value);
+        item = match modify(item, s, (key, value - 1), (value, pos)) {
+            Some(item) => item,
+            None => return Err("date format: unknown modifier"),
+        };
+    }
+}
+
+const fn component(s: &[u8], start: usize, end: usize) -> Option<Item> {
+    let item = if eq(s, (start, end), b"year") {
+        Item::Year { last_two: false }
+    } else if eq(s, (start, end), b"month") {
+        Item::Month {
+            repr: MONTH_NUMERICAL,
+            padding: Padding::Zero,
+        }
+    } else if eq(s, (start, end), b"day") {
+        Item::Day(Padding::Zero)
+    } else if eq(s, (start, end), b"ordinal") {
+        Item::Ordinal(Padding::Zero)
+    } else if eq(s, (start, end), b"weekday") {
+        Item::Weekday { short: false }
+    } else if eq(s, (start, end), b"hour") {
+        Item::Hour {
+            twelve: false,
+            padding: Padding::Zero,
+        }
+    } else if eq(s, (start, end), b"minute") {
+        Item::Minute(Padding::Zero)
+    } else if eq(s, (start, end), b"second") {
+        Item::Second(Padding::Zero)
+    } else if eq(s, (start, end), b"subsecond") {
+        Item::Subsecond { digits: 0 }
+    } else if eq(s, (start, end), b"period") {
+        Item::Period { lower: false }
+    } else if eq(s, (start, end), b"offset_hour") {
+        Item::OffsetHour { mandatory: false }
+    } else if eq(s, (start, end), b"offset_minute") {
+        Item::OffsetMinute
+    } else if eq(s, (start, end), b"unix_timestamp") {
+        Item::UnixTimestamp
+    } else {
+        return None;
+    };
+    Some(item)
+}
+
+const fn modify(item: Item, s: &[u8], key: (usize, usize), value: (usize, usize)) -> Option<Item> {
+    if eq(s, key, b"padding") {
+        let padding = if eq(s, value, b"zero") {
+            Padding::Zero
+        } else if eq(s, value, b"space") {
+            Padding::Space
+        } else if eq(s, value, b"none") {
+            Padding::None
+        } else {
+            return None;
+        };
+        return match item {
+            Item::Month { repr, .. } => Some(Item::Month { repr, padding }),
+            Item::Day(_) => Some(Item::Day(padding)),
+            Item::Ordinal(_) => Some(Item::Ordinal(padding)),
+            Item::Hour { twelve, .. } => Some(Item::Hour { twelve, padding }),
+            Item::Minute(_) => Some(Item::Minute(padding)),
+            Item::Second(_) => Some(Item::Second(padding)),
+            _ => None,
+        };
+    }
+
+    match item {
+        Item::Year { .. } if eq(s, key, b"repr") => {
+            if eq(s, value, b"full") {
+                Some(Item::Year { last_two: false })
+            } else if eq(s, value, b"last_two") {
+                Some(Item::Year { last_two: true })
+            } else {
+                None
+            }
+        },
+        Item::Month { padding, .. } if eq(s, key, b"repr") => {
+            let repr = if eq(s, value, b"numerical") {
+                MONTH_NUMERICAL
+            } else if eq(s, value, b"short") {
+                MONTH_SHORT
+            } else if eq(s, value, b"long") {
+                MONTH_LONG
+            } else {
+                return None;
+            };
+            Some(Item::Month { repr, padding })
+        },
+        Item::Weekday { .. } if eq(s, key, b"repr") => {
+            if eq(s, value, b"long") {
+                Some(Item::Weekday { short: false })
+            } else if eq(s, value, b"short") {
+                Some(Item::Weekday { short: true })
+            } else {
+                None
+            }
+        },
+        Item::Hour { padding, .. } if eq(s, key, b"repr") => {
+            if eq(s, value, b"24") {
+                Some(Item::Hour {
+                    twelve: false,
+                    padding,
+                })
+            } else if eq(s, value, b"12") {
+                Some(Item::Hour {
+                    twelve: true,
+                    padding,
+                })
+            } else {
+                None
+            }
+        },
+        Item::Subsecond { .. } if eq(s, key, b"digits") => {
+            if eq(s, value, b"one_or_more") {
+                Some(Item::Subsecond { digits: 0 })
+            } else if value.1 == value.0 + 1 && s[value.0] >= b'1' && s[value.0] <= b'9' {
+                Some(Item::Subsecond {
+                    digits: s[value.0] - b'0',
+                })
+            } else {
+                None
+            }
+        },
+        Item::Period { .. } if eq(s, key, b"case") => {
+            if eq(s, value, b"upper") {
+                Some(Item::Period { lower: false })
+            } else if eq(s, value, b"lower") {
+                Some(Item::Period { lower: true })
+            } else {
+                None
+            }
+        },
+        Item::OffsetHour { .. } if eq(s, key, b"sign") => {
+            if eq(s, value, b"automatic") {
+                Some(Item::OffsetHour { mandatory: false })
+            } else if eq(s, value, b"mandatory") {
+                Some(Item::OffsetHour { mandatory: true })
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+/// `s[start..end] == expected`
+const fn eq(s: &[u8], (start, end): (usize, usize), expected: &[u8]) -> bool {
+    if end - start != expected.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < expected.len() {
+        if s[start + index] != expected[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+const fn is_whitespace(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+const fn skip_whitespace(s: &[u8], mut pos: usize) -> usize {
+    while pos < s.len() && is_whitespace(s[pos]) {
+        pos += 1;
+    }
+    pos
+}
+
+const fn skip_word(s: &[u8], mut pos: usize) -> usize {
+    while pos < s.len() && s[pos] != b']' && !is_whitespace(s[pos]) {
+        pos += 1;
+    }
+    pos
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+const _: () = {
+    impl DateTimeValue for time::OffsetDateTime {
+        fn parts(&self) -> DateTimeParts {
+            DateTimeParts {
+                offset: self.offset().whole_seconds(),
+                unix_timestamp: self.unix_timestamp(),
+                ..time_parts(self.date(), self.time())
+            }
+        }
+    }
+
+    impl DateTimeValue for time::PrimitiveDateTime {
+        fn parts(&self) -> DateTimeParts {
+            DateTimeParts {
+                unix_timestamp: self.assume_utc().unix_timestamp(),
+                ..time_parts(self.date(), self.time())
+            }
+        }
+    }
+
+    impl DateTimeValue for time::Date {
+        fn parts(&self) -> DateTimeParts {
+            DateTimeParts {
+                unix_timestamp: self.midnight().assume_utc().unix_timestamp(),
+                ..time_parts(*self, time::Time::MIDNIGHT)
+            }
+        }
+    }
+
+    fn time_parts(date: time::Date, time: time::Time) -> DateTimeParts {
+        DateTimeParts {
+            year: date.year(),
+            month: date.month().into(),
+            day: date.day(),
+            ordinal: date.ordinal(),
+            weekday: date.weekday().number_days_from_monday(),
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+            nanosecond: time.nanosecond(),
+            offset: 0,
+            unix_timestamp: 0,
+        }
+    }
+};
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+const _: () = {
+    use chrono::{Datelike, Offset, TimeZone, Timelike};
+
+    impl<Tz: TimeZone> DateTimeValue for chrono::DateTime<Tz> {
+        fn parts(&self) -> DateTimeParts {
+            DateTimeParts {
+                offset: self.offset().fix().local_minus_utc(),
+                unix_timestamp: self.timestamp(),
+                ..chrono_parts(&self.naive_local())
+            }
+        }
+    }
+
+    impl DateTimeValue for chrono::NaiveDateTime {
+        fn parts(&self) -> DateTimeParts {
+            DateTimeParts {
+                unix_timestamp: self.and_utc().timestamp(),
+                ..chrono_parts(self)
+            }
+        }
+    }
+
+    impl DateTimeValue for chrono::NaiveDate {
+        fn parts(&self) -> DateTimeParts {
+            let midnight = self.and_time(chrono::NaiveTime::MIN);
+            DateTimeParts {
+                unix_timestamp: midnight.and_utc().timestamp(),
+                ..chrono_parts(&midnight)
+            }
+        }
+    }
+
+    fn chrono_parts(value: &chrono::NaiveDateTime) -> DateTimeParts {
+        DateTimeParts {
+            year: value.year(),
+            month: value.month() as u8,
+            day: value.day() as u8,
+            ordinal: value.ordinal() as u16,
+            weekday: value.weekday().num_days_from_monday() as u8,
+            hour: value.hour() as u8,
+            minute: value.minute() as u8,
+            second: value.second() as u8,
+            // a leap second is represented as a nanosecond value >= 1_000_000_000
+            nanosecond: value.nanosecond().min(999_999_999),
+            offset: 0,
+            unix_timestamp: 0,
+        }
+    }
+};
@@ -0,0 +1,189 @@
+use core::fmt;
+
+use super::{Locale, Rounding};
+
+/// The decimal digits of a number, the value is `0.{digits} × 10^point`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Digits {
+    pub(crate) kind: Kind,
+    pub(crate) negative: bool,
+    buf: [u8; 40],
+    len: usize,
+    pub(crate) point: isize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Finite,
+    NaN,
+    Infinite,
+}
+
+impl Digits {
+    /// Parse the output of [`itoa`] or [`ryu`], e.g. `-12`, `0.25`, `1e16`, `1.5e-7`, or `inf`
+    pub(crate) fn parse(s: &str) -> Self {
+        let mut digits = Digits {
+            kind: Kind::Finite,
+            negative: false,
+            buf: [0; 40],
+            len: 0,
+            point: 0,
+        };
+        let s = match s.strip_prefix('-') {
+            Some(s) => {
+                digits.negative = true;
+                s
+            },
+            None => s,
+        };
+        match s {
+            "NaN" => digits.kind = Kind::NaN,
+            "inf" => digits.kind = Kind::Infinite,
+            _ => {},
+        }
+        if digits.kind != Kind::Finite {
+            return digits;
+        }
+
+        let (mantissa, exponent) = match s.find('e') {
+            Some(index) => (&s[..index], s[index + 1..].parse().unwrap_or(0)),
+            None => (s, 0),
+        };
+        let mut point = None;
+        for byte in mantissa.bytes() {
+            if byte == b'.' {
+                point = Some(digits.len);
+            } else if digits.len < digits.buf.len() {
+                digits.buf[digits.len] = byte;
+                digits.len += 1;
+            }
+        }
+        digits.point = point.unwrap_or(digits.len) as isize + exponent;
+
+        let zeros = digits.buf[..digits.len]
+            .iter()
+            .take_while(|&&b| b == b'0')
+            .count();
+        digits.buf.copy_within(zeros..digits.len, 0);
+        digits.len -= zeros;
+        digits.point -= zeros as isize;
+        digits.trim();
+        digits
+    }
+
+    /// The number is zero, or was rounded to zero
+    #[inline]
+    pub(crate) fn is_zero(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove trailing zeros
+    fn trim(&mut self) {
+        while self.len > 0 && self.buf[self.len - 1] == b'0' {
+            self.len -= 1;
+        }
+        if self.len == 0 {
+            self.point = 0;
+        }
+    }
+
+    /// The digit at `index`, where `0` is the first significant digit
+    fn get(&self, index: isize) -> char {
+        if index >= 0 && (index as usize) < self.len {
+            self.buf[index as usize] as char
+        } else {
+            '0'
+        }
+    }
+
+    /// Round to `fraction` fraction digits, a negative value rounds to tens, hundreds, …
+    pub(crate) fn round(&mut self, fraction: isize, rounding: Rounding) {
+        let keep = self.point + fraction;
+        if keep >= self.len as isize {
+            return;
+        }
+
+        let kept = keep.max(0) as usize;
+        let first = if keep >= 0 { self.buf[kept] } else { b'0' };
+        let rest = keep < 0 || self.buf[kept + 1..self.len].iter().any(|&b| b != b'0');
+        let odd = kept > 0 && self.buf[kept - 1] % 2 == 1;
+        let up = match rounding {
+            Rounding::HalfEven => first > b'5' || (first == b'5' && (rest || odd)),
+            Rounding::HalfUp => first >= b'5',
+            Rounding::Up => true,
+            Rounding::Down => false,
+            Rounding::Ceiling => !self.negative,
+            Rounding::Floor => self.negative,
+        };
+
+        self.len = kept;
+        if up {
+            if keep <= 0 {
+                // the number was smaller than one unit of the last kept digit
+                self.buf[0] = b'1';
+                self.len = 1;
+                self.point -= keep - 1;
+                return;
+            }
+            let carry = self.buf[..kept].iter_mut().rev().all(|digit| match *digit {
+                b'9' => {
+                    *digit = b'0';
+                    true
+                },
+                _ => {
+                    *digit += 1;
+                    false
+                },
+            });
+            if carry {
+                self.buf[0] = b'1';
+                self.len = 1;
+                self.point += 1;
+            }
+        }
+        self.trim();
+    }
+
+    /// Write the integer part, optionally with the thousands separators of `grouping`
+    pub(crate) fn write_integer(
+        &self,
+        out: &mut dyn fmt::Write,
+        grouping: Option<&Locale>,
+    ) -> fmt::Result {
+        let len = match self.point {
+            point if point > 0 => point as usize,
+            _ => return out.write_str("0"),
+        };
+        let group = match grouping {
+            Some(locale) if len >= 3 + locale.min_grouping => Some(locale.group),
+            _ => None,
+        };
+        for index in 0..len {
+            if let Some(group) = group {
+                if index > 0 && (len - index) % 3 == 0 {
+                    out.write_str(group)?;
+                }
+            }
+            out.write_char(self.get(index as isize))?;
+        }
+        Ok(())
+    }
+
+    /// Write the fraction part with at least `min_fraction` digits
+    pub(crate) fn write_fraction(
+        &self,
+        out: &mut dyn fmt::Write,
+        decimal: &str,
+        min_fraction: usize,
+    ) -> fmt::Result {
+        let end = (self.len as isize).max(self.point + min_fraction as isize);
+        if end <= self.point {
+            return Ok(());
+        }
+        out.write_str(decimal)?;
+        for index in self.point..end {
+            out.write_char(self.get(index))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use core::fmt::{self, Alignment};
+
+use super::digits::{Digits, Kind};
+use super::{NumberValue, Rounding};
+use crate::filters::pad_with;
+
+/// Select how floats are printed
+///
+/// Without options floats are printed using the shortest representation of [`ryu`](::ryu),
+/// e.g. `0.30000000000000004`, `1e-7`, `NaN` or `inf`.
+///
+/// Use `#[template(float(fixed = 2, nan = "–"))]` to select the format of all floats in the
+/// `{{ value blocks }}` of a template, or [`FloatFormat::format()`] for a single value:
+///
+/// ```rust
+/// use nate::fmt::FloatFormat;
+///
+/// const MONEY: FloatFormat = FloatFormat::new().fixed(2).nan("–");
+///
+/// assert_eq!(MONEY.format(0.1 + 0.2).to_string(), "0.30");
+/// assert_eq!(MONEY.format(f64::NAN).to_string(), "–");
+/// assert_eq!(FloatFormat::new().significant(3).format(1234.5).to_string(), "1230");
+/// assert_eq!(FloatFormat::new().no_exponent().format(1e-7).to_string(), "0.0000001");
+/// ```
+///
+/// Values are rounded to the nearest neighbor, ties to the even neighbor,
+/// like [`Rounding::HalfEven`]. Use [`Number`][super::Number] for more options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatFormat {
+    precision: Precision,
+    nan: Option<&'static str>,
+    infinity: Option<&'static str>,
+    exponent: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Precision {
+    Shortest,
+    Fixed(usize),
+    Significant(usize),
+}
+
+impl FloatFormat {
+    /// The default format, i.e. the shortest representation
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            precision: Precision::Shortest,
+            nan: None,
+            infinity: None,
+            exponent: true,
+        }
+    }
+
+    /// Print exactly `digits` fraction digits, e.g. `fixed(2)` prints `0.1 + 0.2` as `0.30`
+    #[inline]
+    pub const fn fixed(mut self, digits: usize) -> Self {
+        self.precision = Precision::Fixed(digits);
+        self
+    }
+
+    /// Print `digits` significant digits, e.g. `significant(2)` prints `1234.5` as `1200`
+    ///
+    /// At least one digit is printed.
+    #[inline]
+    pub const fn significant(mut self, digits: usize) -> Self {
+        self.precision = Precision::Significant(digits);
+        self
+    }
+
+    /// Print `nan` instead of `NaN`
+    #[inline]
+    pub const fn nan(mut self, nan: &'static str) -> Self {
+        self.nan = Some(nan);
+        self
+    }
+
+    /// Print `infinity` instead of `inf`, negative infinity is prefixed with `-`
+    #[inline]
+    pub const fn infinity(mut self, infinity: &'static str) -> Self {
+        self.infinity = Some(infinity);
+        self
+    }
+
+    /// Never use the exponent notation, e.g. print `1e16` as `10000000000000000`
+    ///
+    /// Floats with a fixed number of fraction or significant digits are never printed with an
+    /// exponent.
+    #[inline]
+    pub const fn no_exponent(mut self) -> Self {
+        self.exponent = false;
+        self
+    }
+
+    /// Print a single value in this format
+    #[inline]
+    pub fn format<T: NumberValue>(self, value: T) -> FormattedFloat<T> {
+        FormattedFloat {
+            value,
+            format: self,
+        }
+    }
+
+    /// Print `repr`, the output of [`itoa`](::itoa) or [`ryu`](::ryu), in this format
+    pub(crate) fn write(&self, repr: &str, out: &mut dyn fmt::Write) -> fmt::Result {
+        let mut digits = Digits::parse(repr);
+        match digits.kind {
+            Kind::NaN => return out.write_str(self.nan.unwrap_or("NaN")),
+            Kind::Infinite => {
+                if digits.negative {
+                    out.write_str("-")?;
+                }
+                return out.write_str(self.infinity.unwrap_or("inf"));
+            },
+            Kind::Finite => {},
+        }
+
+        let min_fraction = match self.precision {
+            Precision::Shortest if self.exponent => return out.write_str(repr),
+            Precision::Shortest => 0,
+            Precision::Fixed(fraction) => {
+                digits.round(fraction as isize, Rounding::HalfEven);
+                fraction
+            },
+            Precision::Significant(significant) => {
+                let significant = significant.max(1) as isize;
+                digits.round(significant - digits.point, Rounding::HalfEven);
+                if digits.is_zero() {
+                    (significant - 1) as usize
+                } else {
+                    (significant - digits.point).max(0) as usize
+                }
+            },
+        };
+
+        if digits.negative && !digits.is_zero() {
+            out.write_str("-")?;
+        }
+        digits.write_integer(out, None)?;
+        digits.write_fraction(out, ".", min_fraction)
+    }
+}
+
+impl Default for FloatFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value printed with a [`FloatFormat`]
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedFloat<T> {
+    value: T,
+    format: FloatFormat,
+}
+
+impl<T: NumberValue> FormattedFloat<T> {
+    fn write(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.value
+            .with_repr(&mut |repr| self.format.write(repr, out))
+    }
+}
+
+impl<T: NumberValue> fmt::Display for FormattedFloat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.width() {
+            Some(width) => {
+                let align = f.align().unwrap_or(Alignment::Right);
+                let fill = f.fill();
+                pad_with(f, width, align, fill, |out| self.write(out))
+            },
+            None => self.write(f),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+/// Separators and suffixes of a locale, used by [`Number`][super::Number]
+///
+/// Only a small set of locales is built in. Use [`Locale::from_tag()`] to select a locale at
+/// runtime, e.g. from the `Accept-Language` header of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub(crate) decimal: &'static str,
+    pub(crate) group: &'static str,
+    pub(crate) min_grouping: usize,
+    pub(crate) percent: &'static str,
+    pub(crate) compact: [&'static str; 4],
+}
+
+impl Locale {
+    /// German, e.g. `1.234,5`, `12 %`, `1,2 Tsd.`
+    pub const DE: Locale = Locale {
+        decimal: ",",
+        group: ".",
+        min_grouping: 1,
+        percent: "\u{a0}%",
+        compact: ["\u{a0}Tsd.", "\u{a0}Mio.", "\u{a0}Mrd.", "\u{a0}Bio."],
+    };
+    /// Swiss German, e.g. `1’234.5`, `12%`, `1.2 Tsd.`
+    pub const DE_CH: Locale = Locale {
+        decimal: ".",
+        group: "\u{2019}",
+        min_grouping: 1,
+        percent: "%",
+        compact: ["\u{a0}Tsd.", "\u{a0}Mio.", "\u{a0}Mrd.", "\u{a0}Bio."],
+    };
+    /// English, e.g. `1,234.5`, `12%`, `1.2k`
+    pub const EN: Locale = Locale {
+        decimal: ".",
+        group: ",",
+        min_grouping: 1,
+        percent: "%",
+        compact: ["k", "M", "B", "T"],
+    };
+    /// Spanish, e.g. `1234,5`, `12.345,6`, `12 %`, `1,2 mil`
+    pub const ES: Locale = Locale {
+        decimal: ",",
+        group: ".",
+        min_grouping: 2,
+        percent: "\u{a0}%",
+        compact: ["\u{a0}mil", "\u{a0}M", "\u{a0}mil\u{a0}M", "\u{a0}B"],
+    };
+    /// French, e.g. `1 234,5`, `12 %`, `1,2 k`
+    pub const FR: Locale = Locale {
+        decimal: ",",
+        group: "\u{202f}",
+        min_grouping: 1,
+        percent: "\u{202f}%",
+        compact: ["\u{a0}k", "\u{a0}M", "\u{a0}Md", "\u{a0}Bn"],
+    };
+    /// Dutch, e.g. `1.234,5`, `12%`, `1,2K`
+    pub const NL: Locale = Locale {
+        decimal: ",",
+        group: ".",
+        min_grouping: 1,
+        percent: "%",
+        compact: ["K", "\u{a0}mln.", "\u{a0}mld.", "\u{a0}bln."],
+    };
+    /// Portuguese, e.g. `1.234,5`, `12%`, `1,2 mil`
+    pub const PT: Locale = Locale {
+        decimal: ",",
+        group: ".",
+        min_grouping: 1,
+        percent: "%",
+        compact: ["\u{a0}mil", "\u{a0}mi", "\u{a0}bi", "\u{a0}tri"],
+    };
+
+    /// Look up a built-in locale by its language tag, e.g. `"de-CH"` or `"pt_BR"`
+    ///
+    /// If there is no locale for the region, then the locale of the language is returned.
+    /// The lookup is case insensitive.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let (language, region) = match tag.find(|c| c == '-' || c == '_') {
+            Some(index) => (&tag[..index], Some(&tag[index + 1..])),
+            None => (tag, None),
+        };
+        let mut found = None;
+        for &(lang, reg, locale) in LOCALES {
+            if !lang.eq_ignore_ascii_case(language) {
+                continue;
+            }
+            match (reg, region) {
+                (Some(reg), Some(region)) if reg.eq_ignore_ascii_case(region) => {
+                    return Some(locale);
+                },
+                (None, _) => found = Some(locale),
+                _ => {},
+            }
+        }
+        found
+    }
+}
+
+impl Default for Locale {
+    #[inline]
+    fn default() -> Self {
+        Locale::EN
+    }
+}
+
+const LOCALES: &[(&str, Option<&str>, Locale)] = &[
+    ("de", None, Locale::DE),
+    ("de", Some("CH"), Locale::DE_CH),
+    ("de", Some("LI"), Locale::DE_CH),
+    ("en", None, Locale::EN),
+    ("es", None, Locale::ES),
+    ("fr", None, Locale::FR),
+    ("nl", None, Locale::NL),
+    ("pt", None, Locale::PT),
+];
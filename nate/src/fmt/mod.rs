@@ -0,0 +1,25 @@
+//! Formatting adaptors for values in templates
+//!
+//! The adaptors don't allocate. Like the [filters][crate::filters] they can be used as plain
+//! [`Display`][core::fmt::Display] adaptors outside of templates, too.
+//!
+//! ```rust
+//! use nate::fmt::{Locale, Number};
+//!
+//! let total = Number::new(1234567.891).precision(2).locale(Locale::DE);
+//! assert_eq!(total.to_string(), "1.234.567,89");
+//! ```
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod date;
+mod digits;
+mod float;
+mod locale;
+mod number;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "chrono", feature = "time"))))]
+pub use self::date::{Date, DateFormat, DateTimeValue, Relative};
+pub use self::float::{FloatFormat, FormattedFloat};
+pub use self::locale::Locale;
+pub use self::number::{Number, NumberValue, Rounding, SignDisplay};
@@ -0,0 +1,288 @@
+use core::fmt::{self, Alignment};
+
+use super::digits::{Digits, Kind};
+use super::Locale;
+use crate::filters::pad_with;
+
+/// Print a number with thousands separators, a fixed precision, as percentage, etc.
+///
+/// ```rust
+/// use nate::fmt::{Locale, Number, SignDisplay};
+///
+/// assert_eq!(Number::new(1234567).to_string(), "1,234,567");
+/// assert_eq!(Number::new(1234.5678).precision(2).to_string(), "1,234.57");
+/// assert_eq!(Number::new(0.256).percent().locale(Locale::DE).to_string(), "25,6\u{a0}%");
+/// assert_eq!(Number::new(1234).compact().to_string(), "1.2k");
+/// assert_eq!(Number::new(5).sign(SignDisplay::Always).to_string(), "+5");
+/// ```
+///
+/// Integers are converted using [`itoa`](::itoa), floats using [`ryu`](::ryu).
+/// A float is rounded based on its shortest representation, so `1.005` is rounded to `1.01`
+/// with [`Rounding::HalfUp`], even though its binary value is slightly less than `1.005`.
+///
+/// The output never contains XML special characters, but it is escaped like any other value.
+/// If a width is given in the format specifier, e.g. `{{ Number::new(self.total) :>12 }}`,
+/// then the output is padded accordingly, aligned to the right by default.
+#[derive(Debug, Clone, Copy)]
+pub struct Number<T> {
+    value: T,
+    locale: Locale,
+    grouping: bool,
+    precision: Option<usize>,
+    rounding: Rounding,
+    sign: SignDisplay,
+    percent: bool,
+    compact: bool,
+}
+
+/// How to round a number if it has more fraction digits than the [precision][Number::precision]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round to the nearest neighbor, ties to the even neighbor (the default), `2.5` → `2`
+    HalfEven,
+    /// Round to the nearest neighbor, ties away from zero, `2.5` → `3`, `-2.5` → `-3`
+    HalfUp,
+    /// Round away from zero, `2.1` → `3`, `-2.1` → `-3`
+    Up,
+    /// Round towards zero, `2.9` → `2`, `-2.9` → `-2`
+    Down,
+    /// Round towards positive infinity, `2.1` → `3`, `-2.9` → `-2`
+    Ceiling,
+    /// Round towards negative infinity, `2.9` → `2`, `-2.1` → `-3`
+    Floor,
+}
+
+impl Default for Rounding {
+    #[inline]
+    fn default() -> Self {
+        Rounding::HalfEven
+    }
+}
+
+/// When to print the sign of a [`Number`]
+///
+/// A number that is rounded to zero counts as zero, so `-0.001` with a precision of 2 is printed
+/// as `0.00`, not as `-0.00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignDisplay {
+    /// Only print the sign of negative numbers (the default)
+    Auto,
+    /// Always print the sign, zero gets a plus sign
+    Always,
+    /// Print the sign of positive and negative numbers, but not of zero
+    ExceptZero,
+    /// Never print the sign
+    Never,
+}
+
+impl Default for SignDisplay {
+    #[inline]
+    fn default() -> Self {
+        SignDisplay::Auto
+    }
+}
+
+impl<T: NumberValue> Number<T> {
+    /// Wrap a number, printed with the default options
+    ///
+    /// By default the [English locale][Locale::EN] is used, digits are grouped, integers are
+    /// printed without fraction digits, and floats with as many fraction digits as needed.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            locale: Locale::EN,
+            grouping: true,
+            precision: None,
+            rounding: Rounding::HalfEven,
+            sign: SignDisplay::Auto,
+            percent: false,
+            compact: false,
+        }
+    }
+
+    /// Use the separators and suffixes of a locale
+    #[inline]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Enable or disable the thousands separators (enabled by default)
+    #[inline]
+    pub fn grouping(mut self, grouping: bool) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Print exactly `digits` fraction digits
+    #[inline]
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.precision = Some(digits);
+        self
+    }
+
+    /// Select how to round the number if it has more fraction digits than the precision
+    #[inline]
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Select when to print the sign
+    #[inline]
+    pub fn sign(mut self, sign: SignDisplay) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Multiply the number by 100, and print it with a percent sign
+    #[inline]
+    pub fn percent(mut self) -> Self {
+        self.percent = true;
+        self
+    }
+
+    /// Print large numbers in a short form, e.g. `1.2k` or `3.4M`
+    ///
+    /// Unless a precision is given, compact numbers are printed with at most one fraction digit.
+    #[inline]
+    pub fn compact(mut self) -> Self {
+        self.compact = true;
+        self
+    }
+
+    fn write(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.value
+            .with_repr(&mut |repr| self.write_digits(Digits::parse(repr), out))
+    }
+
+    fn write_digits(&self, mut digits: Digits, out: &mut dyn fmt::Write) -> fmt::Result {
+        match digits.kind {
+            Kind::NaN => return out.write_str("NaN"),
+            Kind::Infinite => {
+                write_sign(out, self.sign, digits.negative, false)?;
+                out.write_str("∞")?;
+                return self.write_suffix(out, None);
+            },
+            Kind::Finite => {},
+        }
+
+        if self.percent && !digits.is_zero() {
+            digits.point += 2;
+        }
+
+        let (max_fraction, min_fraction) = match (self.precision, self.compact) {
+            (Some(precision), _) => (Some(precision), precision),
+            (None, true) => (Some(1), 0),
+            (None, false) => (None, 0),
+        };
+
+        let mut scale = 0;
+        if self.compact {
+            while scale < self.locale.compact.len() && digits.point > 3 {
+                digits.point -= 3;
+                scale += 1;
+            }
+        }
+        if let Some(max_fraction) = max_fraction {
+            digits.round(max_fraction as isize, self.rounding);
+        }
+        if self.compact && scale < self.locale.compact.len() && digits.point > 3 {
+            // e.g. 999.96k was rounded to 1000.0k
+            digits.point -= 3;
+            scale += 1;
+        }
+
+        write_sign(out, self.sign, digits.negative, digits.is_zero())?;
+        let grouping = if self.grouping {
+            Some(&self.locale)
+        } else {
+            None
+        };
+        digits.write_integer(out, grouping)?;
+        digits.write_fraction(out, self.locale.decimal, min_fraction)?;
+        self.write_suffix(out, scale.checked_sub(1))
+    }
+
+    fn write_suffix(&self, out: &mut dyn fmt::Write, scale: Option<usize>) -> fmt::Result {
+        if let Some(scale) = scale {
+            out.write_str(self.locale.compact[scale])?;
+        }
+        if self.percent {
+            out.write_str(self.locale.percent)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_sign(
+    out: &mut dyn fmt::Write,
+    sign: SignDisplay,
+    negative: bool,
+    zero: bool,
+) -> fmt::Result {
+    let sign = match (sign, negative && !zero) {
+        (SignDisplay::Never, _) => return Ok(()),
+        (_, true) => "-",
+        (SignDisplay::Always, false) => "+",
+        (SignDisplay::ExceptZero, false) if !zero => "+",
+        _ => return Ok(()),
+    };
+    out.write_str(sign)
+}
+
+impl<T: NumberValue> fmt::Display for Number<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.width() {
+            Some(width) => {
+                let align = f.align().unwrap_or(Alignment::Right);
+                let fill = f.fill();
+                pad_with(f, width, align, fill, |out| self.write(out))
+            },
+            None => self.write(f),
+        }
+    }
+}
+
+/// A number that can be printed with [`Number`] or [`FloatFormat`][super::FloatFormat]
+pub trait NumberValue {
+    /// Call `f` with the output of [`itoa`](::itoa) or [`ryu`](::ryu)
+    #[doc(hidden)]
+    fn with_repr(&self, f: &mut dyn FnMut(&str) -> fmt::Result) -> fmt::Result;
+}
+
+impl<T: NumberValue + ?Sized> NumberValue for &T {
+    #[inline]
+    fn with_repr(&self, f: &mut dyn FnMut(&str) -> fmt::Result) -> fmt::Result {
+        T::with_repr(*self, f)
+    }
+}
+
+macro_rules! impl_number_value {
+    ($($ty:ty => $buffer:ty,)*) => { $(
+        impl NumberValue for $ty {
+            #[inline]
+            fn with_repr(&self, f: &mut dyn FnMut(&str) -> fmt::Result) -> fmt::Result {
+                f(<$buffer>::new().format(*self))
+            }
+        }
+    )* };
+}
+
+impl_number_value! {
+    i8 => itoa::Buffer,
+    i16 => itoa::Buffer,
+    i32 => itoa::Buffer,
+    i64 => itoa::Buffer,
+    i128 => itoa::Buffer,
+    isize => itoa::Buffer,
+    u8 => itoa::Buffer,
+    u16 => itoa::Buffer,
+    u32 => itoa::Buffer,
+    u64 => itoa::Buffer,
+    u128 => itoa::Buffer,
+    usize => itoa::Buffer,
+    f32 => ryu::Buffer,
+    f64 => ryu::Buffer,
+}
@@ -0,0 +1,47 @@
+use core::fmt;
+use std::borrow::Borrow;
+use std::string::ToString;
+use std::vec::Vec;
+
+use fluent_bundle::bundle::FluentBundle;
+use fluent_bundle::memoizer::MemoizerKind;
+use fluent_bundle::types::FluentNumber;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+
+use super::{Localizer, Message};
+
+impl<R, M> Localizer for FluentBundle<R, M>
+where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    fn write_message(&self, mut output: &mut dyn fmt::Write, message: &Message<'_>) -> fmt::Result {
+        let pattern = self
+            .get_message(message.id())
+            .and_then(|fluent_message| fluent_message.value());
+        let pattern = match pattern {
+            Some(pattern) if message.is_fluent() => pattern,
+            _ => return message.write_untranslated(output),
+        };
+
+        let mut args = FluentArgs::with_capacity(message.args().len());
+        for arg in message.args() {
+            let value = arg.value().to_string();
+            // Only the type makes a number. Its text can still be something else, e.g. the `nan`
+            // replacement of a float format.
+            let number = match arg.is_number() {
+                true => value.parse::<FluentNumber>().ok(),
+                false => None,
+            };
+            let value = match number {
+                Some(number) => FluentValue::Number(number),
+                None => FluentValue::String(value.into()),
+            };
+            args.set(arg.name(), value);
+        }
+
+        // Errors are rendered inline by Fluent, e.g. an unknown variable as `{$name}`.
+        let mut errors = Vec::new();
+        self.write_pattern(&mut output, pattern, Some(&args), &mut errors)
+    }
+}
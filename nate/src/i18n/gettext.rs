@@ -0,0 +1,273 @@
+use core::fmt;
+use std::collections::HashMap;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use intl_pluralrules::operands::PluralOperands;
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+use unic_langid::LanguageIdentifier;
+
+use super::{Localizer, Message};
+
+/// The translations of a gettext `.po` file
+///
+/// ```rust
+/// use nate::i18n::Catalog;
+///
+/// let catalog = Catalog::parse("de", r#"
+/// msgid "Hello, {name}!"
+/// msgstr "Hallo, {name}!"
+///
+/// msgid "One new message"
+/// msgid_plural "{count} new messages"
+/// msgstr[0] "Eine neue Nachricht"
+/// msgstr[1] "{count} neue Nachrichten"
+/// "#).unwrap();
+/// assert_eq!(catalog.get("Hello, {name}!", None), Some("Hallo, {name}!"));
+/// assert_eq!(catalog.get("One new message", Some("1")), Some("Eine neue Nachricht"));
+/// assert_eq!(catalog.get("One new message", Some("3")), Some("{count} neue Nachrichten"));
+/// ```
+///
+/// The plural forms `msgstr[N]` are selected with the
+/// [CLDR plural rules](https://cldr.unicode.org/index/cldr-spec/plural-rules) of the locale,
+/// not with the `Plural-Forms` header of the file. The forms are expected in the order of the
+/// CLDR categories that the locale uses for integers: zero, one, two, few, many, other.
+/// This is the order of the `Plural-Forms` of most languages, e.g. English, German, French,
+/// Polish, Russian or Arabic. Surplus categories select the last form.
+///
+/// Entries that are marked as `fuzzy`, and entries with a `msgctxt` are ignored.
+pub struct Catalog {
+    rules: PluralRules,
+    /// The categories that the locale uses for integers, in CLDR order
+    categories: Vec<u8>,
+    messages: HashMap<String, Vec<String>>,
+}
+
+/// An error returned by [`Catalog::parse()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogError {
+    /// The locale is unknown or has no plural rules
+    Locale,
+    /// Syntax error in the `.po` file at the line
+    Syntax(usize),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Locale => f.write_str("unknown locale"),
+            CatalogError::Syntax(line) => write!(f, "syntax error in line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+impl Catalog {
+    /// Parse the content of a `.po` file with the translations for `locale`, e.g. `"pt-BR"`
+    pub fn parse(locale: &str, source: &str) -> Result<Self, CatalogError> {
+        let locale = locale
+            .parse::<LanguageIdentifier>()
+            .map_err(|_| CatalogError::Locale)?;
+        let rules = PluralRules::create(locale.clone(), PluralRuleType::CARDINAL)
+            .or_else(|_| {
+                let language = LanguageIdentifier::from_parts(locale.language, None, None, &[]);
+                PluralRules::create(language, PluralRuleType::CARDINAL)
+            })
+            .map_err(|_| CatalogError::Locale)?;
+
+        let mut categories = Vec::new();
+        for n in 0..=1000_u32 {
+            if let Ok(category) = rules.select(n) {
+                let category = category_index(&category);
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+        }
+        categories.sort_unstable();
+
+        let mut messages = HashMap::new();
+        for entry in parse_po(source)? {
+            let id = match entry.id {
+                Some(id) if !id.is_empty() => id,
+                _ => continue,
+            };
+            if entry.fuzzy || entry.context.is_some() || entry.strs.iter().all(String::is_empty) {
+                continue;
+            }
+            let _ = messages.insert(id, entry.strs);
+        }
+        Ok(Self {
+            rules,
+            categories,
+            messages,
+        })
+    }
+
+    /// The translation of `id`, with the plural form for `count` if given
+    pub fn get(&self, id: &str, count: Option<&str>) -> Option<&str> {
+        let strs = self.messages.get(id)?;
+        let index = match count {
+            Some(count) => self.plural_index(count),
+            None => 0,
+        };
+        let text = strs.get(index).or_else(|| strs.last())?;
+        if text.is_empty() { None } else { Some(text) }
+    }
+
+    fn plural_index(&self, count: &str) -> usize {
+        let category = PluralOperands::try_from(count)
+            .ok()
+            .and_then(|operands| self.rules.select(operands).ok())
+            .unwrap_or(PluralCategory::OTHER);
+        let category = category_index(&category);
+        match self.categories.iter().position(|&c| c == category) {
+            Some(index) => index,
+            None => self.categories.len().saturating_sub(1),
+        }
+    }
+}
+
+impl fmt::Debug for Catalog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Catalog")
+            .field("locale", self.rules.get_locale())
+            .field("messages", &self.messages)
+            .finish()
+    }
+}
+
+impl Localizer for Catalog {
+    fn write_message(&self, output: &mut dyn fmt::Write, message: &Message<'_>) -> fmt::Result {
+        let count = match message.plural() {
+            Some(_) => message.arg("count").map(|arg| arg.value().to_string()),
+            None => None,
+        };
+        let text = match self.get(message.id(), count.as_deref()) {
+            Some(text) if message.is_gettext() => text,
+            _ => return message.write_untranslated(output),
+        };
+        message.write_text(output, text)
+    }
+}
+
+fn category_index(category: &PluralCategory) -> u8 {
+    match category {
+        PluralCategory::ZERO => 0,
+        PluralCategory::ONE => 1,
+        PluralCategory::TWO => 2,
+        PluralCategory::FEW => 3,
+        PluralCategory::MANY => 4,
+        PluralCategory::OTHER => 5,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    context: Option<String>,
+    id: Option<String>,
+    strs: Vec<String>,
+    fuzzy: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    Plural,
+    Str(usize),
+}
+
+fn parse_po(source: &str) -> Result<Vec<Entry>, CatalogError> {
+    let mut entries = Vec::new();
+    let mut entry = Entry::default();
+    let mut field = None;
+    for (index, line) in source.lines().enumerate() {
+        let err = CatalogError::Syntax(index + 1);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        } else if let Some(flags) = line.strip_prefix("#,") {
+            if !entry.strs.is_empty() {
+                entries.push(core::mem::take(&mut entry));
+            }
+            entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            continue;
+        } else if line.starts_with('#') {
+            continue;
+        }
+
+        let (keyword, value) = match line.find(char::is_whitespace) {
+            Some(_) if line.starts_with('"') => ("", line),
+            Some(pos) => (&line[..pos], line[pos..].trim_start()),
+            None if line.starts_with('"') => ("", line),
+            None => return Err(err),
+        };
+        let value = unquote(value).ok_or(err)?;
+        let next = match keyword {
+            "" => {
+                match field.ok_or(err)? {
+                    Field::Context => entry.context.get_or_insert_with(String::new),
+                    Field::Id => entry.id.get_or_insert_with(String::new),
+                    Field::Plural => continue,
+                    Field::Str(index) => &mut entry.strs[index],
+                }
+                .push_str(&value);
+                continue;
+            },
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::Plural,
+            "msgstr" => Field::Str(0),
+            _ => {
+                let index = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse().ok())
+                    .ok_or(err)?;
+                Field::Str(index)
+            },
+        };
+
+        match next {
+            Field::Context | Field::Id if !entry.strs.is_empty() => {
+                entries.push(core::mem::take(&mut entry));
+            },
+            Field::Str(index) if index != entry.strs.len() => return Err(err),
+            _ => {},
+        }
+        match next {
+            Field::Context => entry.context = Some(value),
+            Field::Id => entry.id = Some(value),
+            Field::Plural => {},
+            Field::Str(_) => entry.strs.push(value),
+        }
+        field = Some(next);
+    }
+    if !entry.strs.is_empty() {
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Parse a C-like string literal
+fn unquote(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        result.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            c @ ('\\' | '"' | '\'' | '?') => c,
+            _ => return None,
+        });
+    }
+    Some(result)
+}
@@ -0,0 +1,348 @@
+//! Translated messages in `{< t "message-id" … >}` and `{< _ "Message" … >}` blocks
+//!
+//! ## Fluent
+//!
+//! With the feature `fluent`, a `{< t … >}` block prints a [Fluent](https://projectfluent.org/)
+//! message in the locale that is selected at render time:
+//!
+//! ```ftl
+//! # locales/en/main.ftl
+//! welcome-user = Welcome, <b>{ $user }</b>!
+//! ```
+//!
+//! ```jinja
+//! {# templates/welcome.html #}
+//! <p>{< t "welcome-user" user = self.name >}</p>
+//! ```
+//!
+//! ```ignore
+//! let template = Welcome { name: "<World>" };
+//! let output = Localized::new(&template, &bundle).to_string();
+//! assert_eq!(output, "<p>Welcome, <b>&#60;World&#62;</b>!</p>");
+//! ```
+//!
+//! The message id and the arguments are checked at compile time against the `.ftl` files in the
+//! directory `locales`, or the directory selected with `#[template(fluent = "…")]`.
+//! Every argument must be used by the message in at least one locale, and every variable of the
+//! message must be passed as argument.
+//!
+//! Integers and floats stay numbers, so they can select a plural form,
+//! e.g. `{ $count -> [one] … *[other] … }`. All other arguments are strings, even if their text
+//! looks like a number, e.g. a phone number `"+49…"` as `&str`.
+//! Fluent wraps the arguments in Unicode isolation marks, unless you disable them with
+//! [`FluentBundle::set_use_isolating()`](fluent_bundle::bundle::FluentBundle::set_use_isolating).
+//!
+//! ## gettext
+//!
+//! With the feature `gettext`, a `{< _ … >}` block marks a translatable text, that is looked up
+//! in a [`Catalog`] of a `.po` file at render time. The text is its own message id, and
+//! placeholders like `{name}` are replaced by the arguments:
+//!
+//! ```jinja
+//! <p>{< _ "Welcome, <b>{user}</b>!" user = self.name >}</p>
+//! <p>{< _ "You have one new message." "You have {count} new messages." count = self.unread >}</p>
+//! ```
+//!
+//! A second text is the plural form, selected by the argument `count` with the
+//! [CLDR plural rules](https://cldr.unicode.org/index/cldr-spec/plural-rules) of the locale.
+//! Placeholders and arguments are checked at compile time.
+//! With `#[template(pot = "po/welcome.pot")]` the texts of the template are extracted into a
+//! `.pot` file at build time, which translators can merge into their `.po` files. A nested
+//! template is derived on its own, so it needs the same `pot` path to add its texts to the file.
+//! Texts of other templates in the file are kept.
+//! The `.pot` file does not know the language, so a plural text has the two forms `msgstr[0]`
+//! and `msgstr[1]`: the translator gives it as many forms as the CLDR categories of their
+//! language, in the order described at [`Catalog`], and the `Plural-Forms` header is left to
+//! them, too.
+//! Untranslated texts are printed as they are.
+//!
+//! ## Rendering
+//!
+//! In both cases the arguments are escaped like values in `{{ value blocks }}`, but the
+//! translated text is printed verbatim, so it can contain markup.
+//!
+//! A template has to be wrapped in [`Localized`] to select the locale. Nested templates are
+//! rendered with the same locale. Without a locale, a Fluent message prints its id, and a gettext
+//! text prints the untranslated text.
+
+#[cfg(feature = "fluent")]
+mod fluent;
+#[cfg(feature = "gettext")]
+mod gettext;
+
+use core::fmt;
+use std::string::ToString;
+
+#[cfg(feature = "gettext")]
+pub use self::gettext::{Catalog, CatalogError};
+use crate::details::WriteAny;
+use crate::{RenderInto, RenderWith};
+
+/// Translates the messages of `{< t "message-id" … >}` or `{< _ "Message" … >}` blocks
+///
+/// This trait is implemented for [`FluentBundle`](fluent_bundle::bundle::FluentBundle) and its
+/// concurrent variant, and for a gettext [`Catalog`].
+pub trait Localizer {
+    /// Write the translated `message` into `output`
+    ///
+    /// The values of the arguments are already escaped.
+    /// If the message is unknown, use [`Message::write_untranslated()`].
+    fn write_message(&self, output: &mut dyn fmt::Write, message: &Message<'_>) -> fmt::Result;
+}
+
+impl<T: Localizer + ?Sized> Localizer for &T {
+    #[inline]
+    fn write_message(&self, output: &mut dyn fmt::Write, message: &Message<'_>) -> fmt::Result {
+        T::write_message(self, output, message)
+    }
+}
+
+/// A translated message, i.e. the content of a `{< t … >}` or `{< _ … >}` block
+#[derive(Debug, Clone, Copy)]
+pub struct Message<'a> {
+    kind: Kind,
+    id: &'a str,
+    plural: Option<&'a str>,
+    args: &'a [Argument<'a>],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Fluent,
+    Gettext,
+}
+
+impl<'a> Message<'a> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn fluent(id: &'a str, args: &'a [Argument<'a>]) -> Self {
+        Self {
+            kind: Kind::Fluent,
+            id,
+            plural: None,
+            args,
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn gettext(id: &'a str, plural: Option<&'a str>, args: &'a [Argument<'a>]) -> Self {
+        Self {
+            kind: Kind::Gettext,
+            id,
+            plural,
+            args,
+        }
+    }
+
+    /// The message is a Fluent message of a `{< t … >}` block
+    #[inline]
+    pub fn is_fluent(&self) -> bool {
+        self.kind == Kind::Fluent
+    }
+
+    /// The message is a gettext text of a `{< _ … >}` block
+    #[inline]
+    pub fn is_gettext(&self) -> bool {
+        self.kind == Kind::Gettext
+    }
+
+    /// The id of the message, for gettext texts the untranslated text
+    #[inline]
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// The untranslated plural form of a gettext text
+    #[inline]
+    pub fn plural(&self) -> Option<&'a str> {
+        self.plural
+    }
+
+    /// The arguments of the message
+    #[inline]
+    pub fn args(&self) -> &'a [Argument<'a>] {
+        self.args
+    }
+
+    /// The argument `name`
+    pub fn arg(&self, name: &str) -> Option<&'a Argument<'a>> {
+        self.args.iter().find(|arg| arg.name == name)
+    }
+
+    /// Write the message without a translation
+    ///
+    /// A Fluent message prints its id. A gettext text prints the untranslated text, or its plural
+    /// form if the argument `count` is not `1`, and replaces the placeholders.
+    pub fn write_untranslated(&self, output: &mut dyn fmt::Write) -> fmt::Result {
+        match (self.kind, self.plural) {
+            (Kind::Fluent, _) => output.write_str(self.id),
+            (Kind::Gettext, Some(plural)) => {
+                let count = self.arg("count").map(|arg| arg.value.to_string());
+                match count.as_deref() {
+                    Some("1") => self.write_text(output, self.id),
+                    _ => self.write_text(output, plural),
+                }
+            },
+            (Kind::Gettext, None) => self.write_text(output, self.id),
+        }
+    }
+
+    /// Write `text`, and replace the placeholders `{name}` with the arguments
+    pub fn write_text(&self, output: &mut dyn fmt::Write, mut text: &str) -> fmt::Result {
+        while let Some(start) = text.find('{') {
+            let (before, after) = text.split_at(start);
+            let arg = after[1..]
+                .find('}')
+                .and_then(|end| Some((self.arg(&after[1..end + 1])?, end + 2)));
+            match arg {
+                Some((arg, end)) => {
+                    output.write_str(before)?;
+                    write!(output, "{}", arg.value)?;
+                    text = &after[end..];
+                },
+                None => {
+                    output.write_str(&text[..start + 1])?;
+                    text = &after[1..];
+                },
+            }
+        }
+        output.write_str(text)
+    }
+}
+
+/// A named argument of a translated message
+#[derive(Clone, Copy)]
+pub struct Argument<'a> {
+    name: &'a str,
+    value: &'a dyn fmt::Display,
+    number: bool,
+}
+
+impl<'a> Argument<'a> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(name: &'a str, value: &'a dyn fmt::Display, number: bool) -> Self {
+        Self {
+            name,
+            value,
+            number,
+        }
+    }
+
+    /// The name of the argument
+    #[inline]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The escaped value of the argument
+    #[inline]
+    pub fn value(&self) -> &'a dyn fmt::Display {
+        self.value
+    }
+
+    /// The value of the argument is an integer or a float, decided by its type
+    #[inline]
+    pub fn is_number(&self) -> bool {
+        self.number
+    }
+}
+
+impl fmt::Debug for Argument<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Argument")
+            .field("name", &self.name)
+            .field("value", &format_args!("{}", self.value))
+            .field("number", &self.number)
+            .finish()
+    }
+}
+
+/// Render a template with a [`Localizer`], e.g. a Fluent bundle or a gettext [`Catalog`]
+///
+/// ```ignore
+/// let output = Localized::new(&template, &bundle).to_string();
+/// ```
+#[derive(Clone, Copy)]
+pub struct Localized<'a, T: ?Sized> {
+    template: &'a T,
+    localizer: &'a dyn Localizer,
+}
+
+impl<'a, T: ?Sized> Localized<'a, T> {
+    /// Render `template` with the messages of `localizer`
+    #[inline]
+    pub fn new(template: &'a T, localizer: &'a dyn Localizer) -> Self {
+        Self {
+            template,
+            localizer,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Localized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Localized").finish_non_exhaustive()
+    }
+}
+
+impl<T: RenderInto + ?Sized> RenderInto for Localized<'_, T> {
+    #[inline]
+    fn render_into(&self, output: impl WriteAny) -> fmt::Result {
+        self.template
+            .render_into(WriteLocalized(output, self.localizer))
+    }
+}
+
+impl<T: RenderWith + ?Sized> RenderWith for Localized<'_, T> {
+    type Context = T::Context;
+
+    #[inline]
+    fn render_with_into(&self, ctx: &Self::Context, output: impl WriteAny) -> fmt::Result {
+        self.template
+            .render_with_into(ctx, WriteLocalized(output, self.localizer))
+    }
+}
+
+impl<T: RenderInto + ?Sized> fmt::Display for Localized<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_fmt(f)
+    }
+}
+
+/// The output of a template, that translates messages with a [`Localizer`]
+struct WriteLocalized<'a, W>(W, &'a dyn Localizer);
+
+impl<W: WriteAny> WriteAny for WriteLocalized<'_, W> {
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> fmt::Result {
+        self.0.write_fmt(fmt)
+    }
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+
+    #[inline]
+    fn write_message(&mut self, message: &Message<'_>) -> fmt::Result {
+        self.1.write_message(&mut AsFmtWrite(&mut self.0), message)
+    }
+}
+
+/// Use a [`WriteAny`] as [`fmt::Write`]
+pub(crate) struct AsFmtWrite<'a, W: ?Sized>(pub(crate) &'a mut W);
+
+impl<W: WriteAny + ?Sized> fmt::Write for AsFmtWrite<'_, W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> fmt::Result {
+        self.0.write_fmt(fmt)
+    }
+}
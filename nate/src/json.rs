@@ -0,0 +1,90 @@
+use core::fmt;
+use std::io;
+
+use crate::details::ScriptMarker;
+
+/// Serialize a value as JSON, e.g. `{{ nate::Json(&self.config) }}`
+///
+/// The value is written directly into the output of the template, without an intermediate
+/// [`String`](std::string::String).
+///
+/// The output is safe to be used inside of a `<script>` element, because `<`, `>`, `&`, `'`,
+/// U+2028 and U+2029 are written as escape sequences, e.g. `\u003c`. Only strings can contain
+/// these characters in JSON, so the meaning of the data does not change.
+///
+/// **Inside of a `<script>` element** of an HTML template, `Json` is printed verbatim in
+/// `{{ value blocks }}`, because XML escaping would break the JSON data. Everywhere else, e.g. in
+/// an attribute like `<div data-config="{{ nate::Json(&self.config) }}">`, it is XML escaped like
+/// any other value, because the quotes of the JSON strings would end the attribute value.
+/// A `{{{ raw block }}}` prints it verbatim in any context, so only use it in a `<script>` element.
+///
+/// ```rust
+/// let value = ["</script>", "a & b"];
+/// assert_eq!(
+///     nate::Json(&value).to_string(),
+///     r#"["\u003c/script\u003e","a \u0026 b"]"#,
+/// );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, Copy)]
+pub struct Json<T: ?Sized>(pub T);
+
+impl<T: ?Sized + serde_crate::Serialize> fmt::Display for Json<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = JsonWriter(f);
+        match serde_json::to_writer(&mut output, &self.0) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl<T: ?Sized + serde_crate::Serialize> ScriptMarker for Json<T> {}
+
+/// Forwards the [`io::Write`] output of [`serde_json`] into a [`fmt::Formatter`], and escapes
+/// HTML special characters
+struct JsonWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl JsonWriter<'_, '_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        let mut last = 0;
+        for (index, c) in string.char_indices() {
+            let escaped = match c {
+                '<' => "\\u003c",
+                '>' => "\\u003e",
+                '&' => "\\u0026",
+                '\'' => "\\u0027",
+                '\u{2028}' => "\\u2028",
+                '\u{2029}' => "\\u2029",
+                _ => continue,
+            };
+            self.0.write_str(&string[last..index])?;
+            self.0.write_str(escaped)?;
+            last = index + c.len_utf8();
+        }
+        self.0.write_str(&string[last..])
+    }
+}
+
+impl io::Write for JsonWriter<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        // serde_json only ever splits its output at character boundaries
+        let string = match core::str::from_utf8(buf) {
+            Ok(string) => string,
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        };
+        match self.write_str(string) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(io::Error::new(io::ErrorKind::Other, err)),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -100,6 +100,7 @@
 //!
 //! No new traits are needed, instead `#[derive(Nate)]` primarily works by implementing [`fmt::Display`](core::fmt::Display).
 //! This also makes nesting of NaTE templates possible.
+//! A nested template in a `{{ value block }}` is not escaped again, but rendered directly into the output of its parent.
 //!
 //! A more complex example would be:
 //!
@@ -150,6 +151,47 @@
 //!
 //! Values in `{{{ raw blocks }}}` are printed verbatim.
 //!
+//! With `#[template(escape = "url")]` values in `{{ value blocks }}` are percent-encoded
+//! using [`UrlEscape`] instead, e.g. for query strings in `href` attributes.
+//! Use `escape = "url_path"` for path segments ([`UrlPathEscape`]), or `escape = "url_form"` for
+//! `application/x-www-form-urlencoded` data ([`UrlFormEscape`]).
+//! For inline scripts and styles there are `escape = "js"` for JavaScript strings
+//! ([`JsStringEscape`]), `escape = "css"` for CSS strings ([`CssStringEscape`]), and
+//! `escape = "css_ident"` for CSS identifiers ([`CssIdentEscape`]).
+//! A single value block can select another escaper with a trailing `e("…")` filter,
+//! e.g. `{{ self.query | e("url") }}` or `{{ self.name | e("js") }}`.
+//! Such a value block cannot have a format specifier.
+//!
+//! With `#[template(float(fixed = 2, nan = "–"))]` floats in `{{ value blocks }}` are printed
+//! with two fraction digits, and `NaN` is replaced by `–`, see [`FloatFormat`](fmt::FloatFormat).
+//! Without this setting floats are printed in their shortest representation.
+//!
+//! Values wrapped in [`Safe`], e.g. trusted HTML returned by a helper function, are printed
+//! verbatim in value blocks, too. The same applies to any type that implements [`RawMarker`].
+//! Types whose output never contains XML special characters, e.g. `bool` or
+//! [`IpAddr`](std::net::IpAddr), are printed verbatim without the overhead of escaping.
+//! This only applies to the HTML escaper. With all other escapers, e.g. `escape = "js"`, these values
+//! and nested templates are escaped like any other value, because e.g. HTML is not safe in a JavaScript string.
+//!
+//! A value in `{{ value }}` or `{{{ raw }}}` blocks can be followed by a format specifier, e.g.
+//! `{{ self.price :.2 }}` or `{{ self.id :>8 }}`, which is applied like in `format!("{:.2}", …)`.
+//! In value blocks the formatted output is XML escaped, except for values that are printed verbatim anyway,
+//! e.g. `nate::Safe` or nested templates. Width and precision must be literal integers.
+//! A trailing `+` or `-` that completes the format specifier is a sign flag, e.g. `{{ self.delta :+}}`;
+//! to trim the whitespaces after such a block write a space in front of the marker, e.g. `{{ self.delta :+ -}}`.
+//!
+//! Values in `{{ value }}` and `{{{ raw }}}` blocks can be piped through filters, e.g.
+//! `{{ self.name | upper | my_filter(20) }}`, which is the same as `{{ my_filter(upper(&self.name), 20) }}`.
+//! The names of the filters in [`filters`] always refer to these filters, other filters are looked up
+//! like any other function. A `safe` filter prints the value verbatim.
+//! A `|` that is followed by a name starts a filter, so `{{ a | b }}` is the same as `{{ b(a) }}`.
+//! To use the bitwise or operator with a name on its right side, put it in parentheses: `{{ (a | b) }}`.
+//!
+//! The adaptors in [`fmt`] control how values are printed, e.g.
+//! `{{ Number::new(self.total).precision(2).locale(Locale::DE) }}` prints `1.234,50`.
+//! With the feature `time` or `chrono`, dates can be printed with a format description that is
+//! checked at compile time, e.g. `{{ self.created | date("[year]-[month]-[day]") }}`.
+//!
 //! For values in `{{{{ debug blocks }}}}` their debug message is printed as in `"{:?}"`.
 //!
 //! For values in `{{{{{ verbose blocks }}}}}` their debug message is printed verbose as in `"{:#?}"`.
@@ -159,8 +201,46 @@
 //! If the path starts with "." or "..", the file is searched relative to the current file.
 //! Otherwise it is search in the project root.
 //!
+//! With the feature `fluent`, `{< t "welcome-user" user = self.name >}` prints a translated message
+//! in the locale that is selected at render time with [`i18n::Localized`].
+//! The message and its arguments are checked at compile time against the project's `.ftl` files.
+//! The arguments are escaped like values in `{{ value blocks }}`.
+//!
+//! With the feature `gettext`, `{< _ "Hello, {name}!" name = self.name >}` marks a translatable text,
+//! that is looked up in a gettext catalog at render time. With `#[template(pot = "po/hello.pot")]`
+//! the texts are extracted into a `.pot` file at build time. Nested templates with the same `pot`
+//! path add their texts to the same file.
+//!
+//! With `#[template(context = "RequestContext")]` the template can access a render context as the
+//! variable `ctx`, e.g. `{{ ctx.user }}`. The template is rendered with
+//! `template.render_with(&ctx, &mut output)` of [`RenderWith`], and nested templates receive the same context.
+//! The context is part of the type of the template, so a nested template with another context type,
+//! or a template that is rendered without its context, does not compile. The context type may borrow,
+//! e.g. `context = "RequestContext<'a>"` for a `struct Page<'a>`. A template with a context does not
+//! implement `Display`, so it cannot be passed to filters, where the context would be lost.
+//!
+//! With `#[template(nonce = "ctx.nonce")]` the attribute `nonce="…"` is added to every `<script>` and
+//! `<style>` tag of the template at compile time, and the value is filled in at render time.
+//!
+//! With `#[template(minify = "html")]` the template is minified at compile time: whitespaces are
+//! collapsed, and comments and optional quotes are removed. The content of `<pre>`, `<textarea>`,
+//! `<script>` and `<style>` elements, and the values of the template are kept as they are.
+//! With `minify = "css"` and `minify = "js"` whitespaces and comments of stylesheets and scripts
+//! are removed, while strings, regular expressions and `/*! license comments */` are kept.
+//!
 //! Using hyphens `-` at the start/end of a block, whitespaces before/after the block are trimmed.
 //!
+//! Using tildes `~` only horizontal whitespaces are trimmed, and after the block a single newline, too.
+//! That way a line that only contains a code block does not leave an empty line in the output.
+//!
+//! With `#[template(strip = "blocks")]` the indentation in front of code `{%…%}` and comment `{#…#}` blocks
+//! that start their line is removed, and a single newline after the blocks. Blocks in the middle of a line keep
+//! the spaces in front of them.
+//! Use a plus `+` at the start/end of a block to keep the whitespaces anyway.
+//!
+//! With `#[template(line_statement = "%")]` every line that starts with `%` (optionally indented)
+//! is a code block, e.g. `% for i in 0..10 {`. The newline at the end of the line is removed, too.
+//!
 //! Data blocks `{{…}}` to `{{{{{…}}}}}` and includes `{<…>}` must not be empty.
 //! Code `{%…%}` and comment `{#…#}` blocks may be empty.
 //!
@@ -170,12 +250,32 @@
 //! The generated code is stored in there even if there were parsing errors in the Rust code.
 //! The path is relative to the project root (where your Cargo.toml lives).
 //!
+//! The command line tool [nate-cli](https://github.com/Kijewski/nate/tree/main/nate-cli) prints the generated code without compiling it:
+//! `nate expand --annotate templates/some.html` shows which rows of the template the generated statements come from.
+//! `nate check` finds syntax errors, empty blocks, missing include targets and include cycles in all your templates.
+//!
 //! ## Feature flags
 //!
 //! * `std` <sup>\[enabled by default\]</sup> — enable features found in [`std`] crate, e.g. printing the value of a [`MutexGuard`](std::sync::MutexGuard)
 //!
 //! * `alloc` <sup>\[enabled by default, enabled by `std`\]</sup> — enable features found in the [`alloc`] crate, e.g. [`io::Write`](std::io::Write)
 //!
+//! * `chrono` — print dates and times of the crate [`chrono`](https://docs.rs/chrono/0.4/) with [`fmt::Date`] and [`fmt::Relative`], needs Rust 1.61 like chrono 0.4.35
+//!
+//! * `fluent` — translate `{< t "message-id" … >}` blocks with [Fluent](https://projectfluent.org/), see [`i18n`], implies `std`
+//!
+//! * `gettext` — translate `{< _ "…" >}` blocks with gettext catalogs, see [`i18n`], implies `std`
+//!
+//! * `rust_decimal` — print a [`Decimal`](https://docs.rs/rust_decimal/1/rust_decimal/struct.Decimal.html) without escaping it
+//!
+//! * `serde` — serialize values as JSON with [`Json`], implies `std`
+//!
+//! * `time` — print dates and times of the crate [`time`](https://docs.rs/time/0.3/) with [`fmt::Date`] and [`fmt::Relative`]
+//!
+//! * `unicode-segmentation` — count grapheme clusters instead of characters in [`filters::truncate()`]
+//!
+//! * `uuid` — print a [`Uuid`](https://docs.rs/uuid/1/uuid/struct.Uuid.html) without escaping it
+//!
 
 #[cfg(doc)]
 extern crate alloc;
@@ -187,11 +287,28 @@ pub mod details;
 mod escape;
 mod fast_float;
 mod fast_integer;
+pub mod filters;
+pub mod fmt;
+#[cfg(any(feature = "fluent", feature = "gettext"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "fluent", feature = "gettext"))))]
+pub mod i18n;
+#[cfg(feature = "serde")]
+mod json;
 mod raw;
+mod safe;
 
 pub use ::nate_derive::{addr, Nate};
 
-pub use crate::details::{EscapeWrapper, RenderInto, WriteAny};
+pub use crate::details::{EscapeWrapper, RenderInto, RenderWith, WriteAny};
+pub use crate::escape::{
+    CssIdentEscape, CssStringEscape, JsStringEscape, UrlEscape, UrlFormEscape, UrlPathEscape,
+    XmlEscape,
+};
 pub use crate::fast_float::FloatMarker;
 pub use crate::fast_integer::IntMarker;
+#[cfg(feature = "serde")]
+pub use crate::json::Json;
 pub use crate::raw::RawMarker;
+#[cfg(feature = "alloc")]
+pub use crate::safe::Markup;
+pub use crate::safe::Safe;
@@ -10,67 +10,38 @@ use crate::details::EscapeWrapper;
 impl<T: RawMarker> RawKind for &&&EscapeWrapper<T> {}
 
 /// Types implementing this marker are not escaped, but printed verbatim
-pub trait RawMarker: fmt::Display {
-    #[doc(hidden)]
-    type Escaped: fmt::Display;
+///
+/// This only applies to the HTML escaper. With other escapers, e.g. `escape = "js"`, the value is
+/// escaped like any other value.
+///
+/// The trait has no items, so implement it with an empty `impl` block. In nate 0.4 it had
+/// the hidden items `Escaped` and `escape()`, which were removed.
+pub trait RawMarker: fmt::Display {}
 
-    #[doc(hidden)]
-    fn escape(&self) -> Self::Escaped;
-}
-
-impl<T: RawMarker> RawMarker for &T {
-    type Escaped = T::Escaped;
-
-    fn escape(&self) -> Self::Escaped {
-        T::escape(*self)
-    }
-}
+impl<T: RawMarker> RawMarker for &T {}
 
 #[doc(hidden)]
 pub trait RawKind {
     #[inline]
-    fn wrap<T: RawMarker>(&self, value: &T) -> <T as RawMarker>::Escaped {
-        value.escape()
+    fn wrap<'a, T: RawMarker>(&self, value: &'a T) -> &'a T {
+        value
     }
-}
-
-impl RawMarker for bool {
-    type Escaped = bool;
 
+    /// Print a value verbatim that was formatted with a format specifier, e.g.
+    /// `{{ value :>20 }}`
     #[inline]
-    fn escape(&self) -> Self::Escaped {
-        *self
+    fn wrap_fmt<'a>(&self, formatted: fmt::Arguments<'a>) -> fmt::Arguments<'a> {
+        formatted
     }
 }
 
-impl<T: RawMarker> RawMarker for cell::Ref<'_, T> {
-    type Escaped = T::Escaped;
+impl RawMarker for bool {}
 
-    #[inline]
-    fn escape(&self) -> Self::Escaped {
-        let value: &T = self;
-        value.escape()
-    }
-}
+impl<T: RawMarker> RawMarker for cell::Ref<'_, T> {}
 
-impl<T: RawMarker> RawMarker for cell::RefMut<'_, T> {
-    type Escaped = T::Escaped;
+impl<T: RawMarker> RawMarker for cell::RefMut<'_, T> {}
 
-    #[inline]
-    fn escape(&self) -> Self::Escaped {
-        let value: &T = self;
-        value.escape()
-    }
-}
-
-impl<T: RawMarker> RawMarker for num::Wrapping<T> {
-    type Escaped = T::Escaped;
-
-    #[inline]
-    fn escape(&self) -> Self::Escaped {
-        self.0.escape()
-    }
-}
+impl<T: RawMarker> RawMarker for num::Wrapping<T> {}
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -80,78 +51,51 @@ const _: () = {
         T: RawMarker + alloc::borrow::ToOwned,
         <T as alloc::borrow::ToOwned>::Owned: fmt::Display,
     {
-        type Escaped = T::Escaped;
-
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
     }
 
-    impl<T: RawMarker> RawMarker for alloc::boxed::Box<T> {
-        type Escaped = T::Escaped;
+    impl<T: RawMarker> RawMarker for alloc::boxed::Box<T> {}
 
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
-    }
-
-    impl<T: RawMarker> RawMarker for alloc::rc::Rc<T> {
-        type Escaped = T::Escaped;
-
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
-    }
-
-    impl<T: RawMarker> RawMarker for alloc::sync::Arc<T> {
-        type Escaped = T::Escaped;
+    impl<T: RawMarker> RawMarker for alloc::rc::Rc<T> {}
 
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
-    }
+    impl<T: RawMarker> RawMarker for alloc::sync::Arc<T> {}
 };
 
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 const _: () = {
-    use std::sync;
+    use std::{net, sync};
 
-    impl<T: RawMarker> RawMarker for sync::MutexGuard<'_, T> {
-        type Escaped = T::Escaped;
+    // The output of these types never contains XML special characters. Other escapers, e.g. for
+    // URLs or CSS identifiers, still escape them, because `RawMarker` only applies to HTML.
+    impl RawMarker for net::IpAddr {}
 
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
-    }
+    impl RawMarker for net::Ipv4Addr {}
 
-    impl<T: RawMarker> RawMarker for sync::RwLockReadGuard<'_, T> {
-        type Escaped = T::Escaped;
+    impl RawMarker for net::Ipv6Addr {}
 
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
-    }
+    impl RawMarker for net::SocketAddr {}
 
-    impl<T: RawMarker> RawMarker for sync::RwLockWriteGuard<'_, T> {
-        type Escaped = T::Escaped;
+    impl RawMarker for net::SocketAddrV4 {}
 
-        #[inline]
-        fn escape(&self) -> Self::Escaped {
-            let value: &T = self;
-            value.escape()
-        }
-    }
+    impl RawMarker for net::SocketAddrV6 {}
+
+    impl<T: RawMarker> RawMarker for sync::MutexGuard<'_, T> {}
+
+    impl<T: RawMarker> RawMarker for sync::RwLockReadGuard<'_, T> {}
+
+    impl<T: RawMarker> RawMarker for sync::RwLockWriteGuard<'_, T> {}
+};
+
+#[cfg(feature = "rust_decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+impl RawMarker for rust_decimal::Decimal {}
+
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+const _: () = {
+    impl RawMarker for uuid::Uuid {}
+
+    impl RawMarker for uuid::fmt::Hyphenated {}
+
+    impl RawMarker for uuid::fmt::Simple {}
 };
@@ -0,0 +1,88 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt;
+
+use crate::details::XmlEscape;
+use crate::RawMarker;
+
+/// A value that is safe to be printed verbatim, even in `{{ value blocks }}`
+///
+/// This applies to the HTML escaper. With other escapers, e.g. `escape = "js"`, a `Safe` value is
+/// escaped like any other value, because markup is not safe in e.g. a JavaScript string.
+///
+/// Use this type to return trusted HTML from helper functions, so that templates can print it
+/// with a plain `{{ value block }}` instead of a `{{{ raw block }}}`.
+///
+/// ```rust
+/// use nate::{Markup, Safe};
+///
+/// fn bold(text: &str) -> Markup {
+///     Safe::trusted(format!("<b>{}</b>", Safe::escape(text)))
+/// }
+///
+/// assert_eq!(bold("<World>").to_string(), "<b>&#60;World&#62;</b>");
+/// ```
+///
+/// A `Safe` value can only be constructed from escaped content with [`Safe::escape()`], or
+/// explicitly with [`Safe::trusted()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Safe<T>(T);
+
+/// Owned HTML that is printed verbatim, even in `{{ value blocks }}`, see [`Safe`]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type Markup = Safe<alloc::string::String>;
+
+impl<T: fmt::Display> Safe<XmlEscape<T>> {
+    /// XML escape the value now, so it can be printed verbatim later
+    #[inline]
+    pub fn escape(value: T) -> Self {
+        Self(XmlEscape(value))
+    }
+}
+
+impl<T: fmt::Display> Safe<T> {
+    /// Trust that the value is escaped already, or contains valid markup
+    ///
+    /// The value must not contain any user input that was not escaped!
+    #[inline]
+    pub fn trusted(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Get a reference to the wrapped value
+    #[inline]
+    pub fn as_inner(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the value
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Render the value into a [`Markup`]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_markup(&self) -> Markup {
+        Safe(alloc::string::ToString::to_string(&self.0))
+    }
+}
+
+impl<T: fmt::Display> From<XmlEscape<T>> for Safe<XmlEscape<T>> {
+    #[inline]
+    fn from(value: XmlEscape<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Safe<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> RawMarker for Safe<T> {}